@@ -0,0 +1,102 @@
+//! Diagnostic rendering for showing where in a source file an error occurred,
+//! see [render_diagnostic] docs for more info
+
+use crate::file_pos::{FilePos, TAB_WIDTH};
+use logos::Span;
+
+/// Renders each source line which the `primary` and `secondary` spans are on,
+/// underlining the primary span with `^` and secondary spans with `-` next to
+/// their messages. Used for errors which reference multiple locations such as
+/// "first defined here"
+pub fn render_diagnostic(
+    source: &str,
+    primary: (Span, &str),
+    secondary: Vec<(Span, &str)>,
+) -> String {
+    let mut labels: Vec<(FilePos, usize, char, &str)> = vec![];
+
+    for ((span, msg), marker) in
+        std::iter::once((primary, '^')).chain(secondary.into_iter().map(|label| (label, '-')))
+    {
+        let pos = FilePos::new(None, source, span.start).unwrap_or(FilePos {
+            path: None,
+            line: source.lines().count().max(1),
+            col: 1,
+        });
+        let width = source
+            .get(span)
+            .map(|found| found.split('\n').next().unwrap().chars().count())
+            .unwrap_or(0);
+
+        labels.push((pos, width.max(1), marker, msg));
+    }
+
+    labels.sort_by_key(|(pos, ..)| pos.line);
+
+    let gutter = labels.last().unwrap().0.line.to_string().len();
+    let mut output = format!("{} |\n", " ".repeat(gutter));
+    let mut last_line = 0;
+
+    for (pos, width, marker, msg) in labels {
+        if pos.line != last_line {
+            output.push_str(&format!(
+                "{:>gutter$} | {}\n",
+                pos.line,
+                expand_tabs(source.lines().nth(pos.line - 1).unwrap_or("")),
+                gutter = gutter
+            ));
+            last_line = pos.line;
+        }
+
+        output.push_str(&format!(
+            "{} | {}{} {}\n",
+            " ".repeat(gutter),
+            " ".repeat(pos.col - 1),
+            marker.to_string().repeat(width),
+            msg
+        ));
+    }
+
+    output
+}
+
+/// Expands tabs in `line` up to their tab stops so underlines match the
+/// columns given by [FilePos]
+fn expand_tabs(line: &str) -> String {
+    let mut output = String::new();
+
+    for c in line.chars() {
+        if c == '\t' {
+            output.push_str(&" ".repeat(TAB_WIDTH - output.chars().count() % TAB_WIDTH));
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiple_labels() {
+        assert_eq!(
+            render_diagnostic(
+                "fun f(a a) {}",
+                (8..9, "duplicate parameter"),
+                vec![(6..7, "first defined here")]
+            ),
+            "  |\n1 | fun f(a a) {}\n  |         ^ duplicate parameter\n  |       - first defined here\n"
+        );
+        assert_eq!(
+            render_diagnostic(
+                "let x = 1\n\tlet x = 2",
+                (15..16, "redefined here"),
+                vec![(4..5, "first defined here")]
+            ),
+            "  |\n1 | let x = 1\n  |     - first defined here\n2 |     let x = 2\n  |         ^ redefined here\n"
+        );
+    }
+}
@@ -2,6 +2,9 @@
 
 use std::{fmt, path::PathBuf};
 
+/// Amount of columns between each tab stop when counting tab characters
+pub const TAB_WIDTH: usize = 4;
+
 /// Represents a specific position in a file with line and column numbers taken
 /// from a range and an optional file for [fmt::Display] usage
 pub struct FilePos {
@@ -12,12 +15,24 @@ pub struct FilePos {
 
 impl FilePos {
     /// Gets a new [FilePos] from given input, the ind to find and the filepath
-    /// to display. If this returns [None], the file ended before expected
+    /// to display. An `ind` of the input's length gives the position just after
+    /// the last character and anything further past the end gives [None]
     pub fn new(path: impl Into<Option<PathBuf>>, input: &str, ind: usize) -> Option<Self> {
+        Self::with_tab_width(path, input, ind, TAB_WIDTH)
+    }
+
+    /// Gets a new [FilePos] similar to [FilePos::new] but with tab characters
+    /// advancing the column to the next stop of `tab_width` columns
+    pub fn with_tab_width(
+        path: impl Into<Option<PathBuf>>,
+        input: &str,
+        ind: usize,
+        tab_width: usize,
+    ) -> Option<Self> {
         let mut line: usize = 1;
         let mut col: usize = 1;
 
-        for (input_ind, c) in input.chars().enumerate() {
+        for (input_ind, c) in input.char_indices() {
             if input_ind == ind {
                 return Some(Self {
                     path: path.into(),
@@ -27,12 +42,22 @@ impl FilePos {
             } else if c == '\n' {
                 line += 1;
                 col = 1;
+            } else if c == '\t' {
+                col += tab_width - (col - 1) % tab_width;
             } else {
                 col += 1;
             }
         }
 
-        None
+        if ind == input.len() {
+            Some(Self {
+                path: path.into(),
+                line,
+                col,
+            })
+        } else {
+            None
+        }
     }
 }
 
@@ -50,3 +75,42 @@ impl fmt::Display for FilePos {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jingo_lib::frontend::lexer::Token;
+    use logos::Logos;
+
+    #[test]
+    fn tab_columns() {
+        let input = "\t\tbad#";
+        let mut lex = Token::lexer(input);
+
+        lex.next(); // bad
+        assert_eq!(lex.next(), Some(Token::Error));
+
+        let pos = FilePos::new(None, input, lex.span().start).unwrap();
+        assert_eq!((pos.line, pos.col), (1, 12));
+
+        let pos = FilePos::with_tab_width(None, input, lex.span().start, 8).unwrap();
+        assert_eq!((pos.line, pos.col), (1, 20));
+
+        let pos = FilePos::new(None, "ab\tc", 3).unwrap();
+        assert_eq!(pos.col, 5);
+    }
+
+    #[test]
+    fn end_of_file() {
+        let pos = FilePos::new(None, "ab\ncd", 5).unwrap();
+        assert_eq!((pos.line, pos.col), (2, 3));
+
+        let pos = FilePos::new(None, "ab\n", 3).unwrap();
+        assert_eq!((pos.line, pos.col), (2, 1));
+
+        let pos = FilePos::new(None, "", 0).unwrap();
+        assert_eq!((pos.line, pos.col), (1, 1));
+
+        assert!(FilePos::new(None, "ab\ncd", 6).is_none());
+    }
+}
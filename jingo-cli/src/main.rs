@@ -3,6 +3,7 @@
 #![deny(unsafe_code)]
 #![deny(warnings)]
 
+mod diagnostic;
 mod file_pos;
 mod subcommand;
 mod utils;
@@ -11,7 +12,7 @@ use file_pos::FilePos;
 use std::{env, process};
 
 /// Help information
-const HELP_INFO: &str = "Usage → jingo [OPTIONS]\n\nA lightweight, high-level language designed for rapid prototyping\n\nOptions ↴\n  run [FILE]\tCompiles & runs a file\n  build [FILE]\tCompiles a file\n  help\t\tShows this help\n\nAdvanced options ↴\n  lex [FILE]\tShow lexing output\n  parse [FILE]\tShow parsing output";
+const HELP_INFO: &str = "Usage → jingo [OPTIONS]\n\nA lightweight, high-level language designed for rapid prototyping\n\nOptions ↴\n  run [FILE]\tCompiles & runs a file\n  build [FILE]\tCompiles a file\n  fmt [FILE]\tFormats a file\n    --write\t\tOverwrite the file instead of printing\n  help\t\tShows this help\n\nAdvanced options ↴\n  lex [FILE]\tShow lexing output\n    --count\t\tShow only the amount of tokens\n  parse [FILE]\tShow parsing output\n    --dump-spans\tShow expression spans instead\n    --dot\t\tShow a GraphViz graph instead";
 
 /// Command to run
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +21,19 @@ pub enum Command {
     Run,
     Lex,
     Parse,
+    Fmt,
+}
+
+impl Command {
+    /// Flags which are recognised when passed to this command
+    pub fn flags(&self) -> &'static [&'static str] {
+        match self {
+            Command::Lex => &["--count"],
+            Command::Parse => &["--dump-spans", "--dot"],
+            Command::Fmt => &["--write"],
+            _ => &[],
+        }
+    }
 }
 
 /// Parsed cli
@@ -35,7 +49,7 @@ pub struct Parsed {
 impl Parsed {
     /// Creates new [Parsed] using default [env::args]
     fn new() -> Self {
-        let mut env_args = env::args().into_iter();
+        let mut env_args = env::args();
         env_args.next(); // skip over first
         Parsed::custom(env_args.collect())
     }
@@ -43,32 +57,44 @@ impl Parsed {
     /// Parses custom arguments
     fn custom(args: Vec<String>) -> Self {
         // TODO: use [OsString]
-        if args.len() == 0 {
+        if args.is_empty() {
             utils::help_exit("No arguments passed");
         }
 
-        match args[0].as_str() {
+        let command = match args[0].as_str() {
             "help" | "--help" | "-h" => {
                 println!("{}", HELP_INFO);
                 process::exit(0)
             }
-            "run" => Self {
-                command: Command::Run,
-                data: args[1..].to_vec(),
-            },
-            "compile" => Self {
-                command: Command::Compile,
-                data: args[1..].to_vec(),
-            },
-            "lex" => Self {
-                command: Command::Lex,
-                data: args[1..].to_vec(),
-            },
-            "parse" => Self {
-                command: Command::Parse,
-                data: args[1..].to_vec(),
-            },
+            "run" => Command::Run,
+            "compile" => Command::Compile,
+            "lex" => Command::Lex,
+            "parse" => Command::Parse,
+            "fmt" => Command::Fmt,
             _ => utils::help_exit(format!("Command '{}' not recognised", args[0])),
+        };
+
+        let data = args[1..].to_vec();
+
+        if let Err(msg) = Self::check_flags(&command, &data) {
+            utils::help_exit(msg)
+        }
+
+        Self { command, data }
+    }
+
+    /// Ensures all flags passed in `data` are recognised by `command`, giving an
+    /// error message for the first one which isn't
+    fn check_flags(command: &Command, data: &[String]) -> Result<(), String> {
+        match data
+            .iter()
+            .find(|arg| arg.starts_with('-') && !command.flags().contains(&arg.as_str()))
+        {
+            Some(flag) => Err(format!(
+                "Flag '{}' not recognised for the {:?} command",
+                flag, command
+            )),
+            None => Ok(()),
         }
     }
 }
@@ -77,9 +103,11 @@ fn main() {
     let parsed = Parsed::new();
 
     match parsed.command {
+        Command::Compile => subcommand::compile::launch(parsed),
         Command::Lex => subcommand::lex::launch(parsed),
         Command::Parse => subcommand::parse::launch(parsed),
-        other => todo!("Finish '{:?}' command", other)
+        Command::Fmt => subcommand::fmt::launch(parsed),
+        other => todo!("Finish '{:?}' command", other),
     }
 }
 
@@ -97,4 +125,32 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn fmt_parse() {
+        assert_eq!(
+            Parsed::custom(vec![
+                "fmt".to_string(),
+                "--write".to_string(),
+                "foo.jno".to_string()
+            ]),
+            Parsed {
+                command: Command::Fmt,
+                data: vec!["--write".to_string(), "foo.jno".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_flags() {
+        assert!(
+            Parsed::check_flags(&Command::Lex, &["--unknown".to_string(), "foo".to_string()])
+                .is_err()
+        );
+        assert!(Parsed::check_flags(&Command::Lex, &["--dump-spans".to_string()]).is_err());
+        assert!(Parsed::check_flags(&Command::Lex, &["--count".to_string()]).is_ok());
+        assert!(Parsed::check_flags(&Command::Parse, &["--dump-spans".to_string()]).is_ok());
+        assert!(Parsed::check_flags(&Command::Parse, &["--dot".to_string()]).is_ok());
+        assert!(Parsed::check_flags(&Command::Lex, &["foo".to_string()]).is_ok());
+    }
 }
@@ -0,0 +1,39 @@
+//! Compile runner
+
+use crate::utils::{help_exit, msg_exit, open_file};
+use crate::{FilePos, Parsed};
+use ansi_term::Style;
+use jingo_lib::frontend::{lexer::Token, parser, symbols};
+use logos::Logos;
+use std::path::PathBuf;
+
+/// Runs compilation steps
+pub fn launch(parsed: Parsed) {
+    if parsed.data.is_empty() {
+        help_exit("No files passed for compiling")
+    } else if parsed.data.len() > 1 {
+        help_exit("More then one file passed for compiling")
+    }
+
+    let path = PathBuf::from(parsed.data[0].clone());
+    let input = &open_file(path.clone());
+
+    let mut lex = Token::lexer(input);
+
+    let exprs = match parser::launch(&mut lex) {
+        Ok(exprs) => exprs,
+        Err(err) => msg_exit(format!(
+            "Error in {}\n{}",
+            FilePos::new(path, input, err.offset().unwrap_or(lex.span().start)).unwrap(),
+            Style::new()
+                .bold()
+                .paint(format!("  Found something whilst parsing → {}", err))
+        )),
+    };
+
+    println!("Symbol table ↴");
+
+    for entry in symbols::symbol_table(&exprs) {
+        println!("- {:?} {} @ {}", entry.kind, entry.name, entry.start);
+    }
+}
@@ -0,0 +1,76 @@
+//! Formatting runner
+
+use crate::utils::{error_exit, help_exit, msg_exit, open_file};
+use crate::{FilePos, Parsed};
+use ansi_term::Style;
+use jingo_lib::frontend::{
+    lexer::{self, Token},
+    parser, print,
+};
+use logos::Logos;
+use std::{fs, path::PathBuf};
+
+/// Runs formatting steps, printing the formatted source or overwriting the
+/// file with it if `--write` is passed. Ordinary `--` comments aren't kept by
+/// the printer, so `--write` is refused for files containing them
+pub fn launch(parsed: Parsed) {
+    let write = parsed.data.iter().any(|arg| arg == "--write");
+    let files: Vec<&String> = parsed.data.iter().filter(|arg| *arg != "--write").collect();
+
+    if files.is_empty() {
+        help_exit("No files passed for formatting")
+    } else if files.len() > 1 {
+        help_exit("More then one file passed for formatting")
+    }
+
+    let path = PathBuf::from(files[0].clone());
+    let input = &open_file(path.clone());
+
+    let mut lex = Token::lexer(input);
+
+    let formatted = match parser::launch(&mut lex) {
+        Ok(exprs) => print::to_source(&exprs),
+        Err(err) => msg_exit(format!(
+            "Error in {}\n{}",
+            FilePos::new(path, input, err.offset().unwrap_or(lex.span().start)).unwrap(),
+            Style::new()
+                .bold()
+                .paint(format!("  Found something whilst parsing → {}", err))
+        )),
+    };
+
+    if write {
+        if let Some(comment) = lexer::collect_comments(input).0.first() {
+            msg_exit(format!(
+                "Error in {}\n{}",
+                FilePos::new(path, input, comment.span.start).unwrap(),
+                Style::new().bold().paint(
+                    "  Refusing to overwrite file as formatting would drop its `--` comments"
+                )
+            ))
+        }
+
+        if let Err(err) = fs::write(&path, formatted) {
+            error_exit(format!("Could not write {:?} file, {}", path, err))
+        }
+    } else {
+        print!("{}", formatted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jingo_lib::frontend::{self, print};
+
+    #[test]
+    fn idempotent() {
+        let input = "--- Adds\n--- two\nfun add(a b) { return a+b }\nclass Hi {   let mut x = 'c' }\nwhile true {if x {break} else {none}}";
+        let formatted = print::to_source(&frontend::parse(input).unwrap());
+
+        assert!(formatted.starts_with("--- Adds\n--- two\nfun add(a b) {"));
+        assert_eq!(
+            print::to_source(&frontend::parse(&formatted).unwrap()),
+            formatted
+        );
+    }
+}
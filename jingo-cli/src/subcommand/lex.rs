@@ -3,35 +3,71 @@
 use crate::utils::{help_exit, msg_exit, open_file};
 use crate::{FilePos, Parsed};
 use ansi_term::Style;
-use jingo_lib::frontend::lexer::Token;
+use jingo_lib::frontend::lexer::{self, Token};
 use logos::Logos;
 use std::path::PathBuf;
 
 /// Runs lexing steps
 pub fn launch(parsed: Parsed) {
-    if parsed.data.len() == 0 {
+    let count = parsed.data.iter().any(|arg| arg == "--count");
+    let files: Vec<&String> = parsed.data.iter().filter(|arg| *arg != "--count").collect();
+
+    if files.is_empty() {
         help_exit("No files passed for lexing")
-    } else if parsed.data.len() > 1 {
+    } else if files.len() > 1 {
         help_exit("More then one file passed for lexing")
     }
 
-    let path = PathBuf::from(parsed.data[0].clone());
+    let path = PathBuf::from(files[0].clone());
     let input = &open_file(path.clone());
 
+    if count {
+        match lexer::count_tokens(input) {
+            Ok(amount) => println!("Lexed {} tokens", amount),
+            Err(span) => msg_exit(format!(
+                "Error in {} ↴\n{}",
+                FilePos::new(path, input, span.start).unwrap(),
+                Style::new().bold().paint(format!(
+                    "  Unknown token was found whilst lexing → {}",
+                    &input[span]
+                ))
+            )),
+        }
+
+        return;
+    }
+
     let mut lex = Token::lexer(input);
     let mut output = vec![];
 
     loop {
         // separate loop in order to print all at once for error consistency
         match lex.next() {
-            Some(Token::Error) => msg_exit(format!(
-                "Error in {} ↴\n{}",
-                FilePos::new(path, input, lex.span().start).unwrap(),
-                Style::new().bold().paint(format!(
-                    "  Unknown token was found whilst lexing → {}",
-                    lex.slice()
-                ))
-            )),
+            Some(Token::Error) => match lex.extras.bad_escape {
+                _ if lexer::unterminated_str(&lex).is_some() => msg_exit(format!(
+                    "Error in {} ↴\n{}",
+                    FilePos::new(path, input, lex.span().start).unwrap(),
+                    Style::new()
+                        .bold()
+                        .paint("  String was never closed whilst lexing → \"")
+                )),
+                Some(ind) => msg_exit(format!(
+                    "Error in {} ↴\n{}",
+                    FilePos::new(path, input, ind).unwrap(),
+                    Style::new().bold().paint(format!(
+                        "  Invalid escape was found whilst lexing → {}",
+                        input[ind..].chars().take(2).collect::<String>()
+                    ))
+                )),
+                None => msg_exit(format!(
+                    "Error in {} ↴\n{}",
+                    FilePos::new(path, input, lex.span().start).unwrap(),
+                    Style::new().bold().paint(format!(
+                        "  Unknown token was found whilst lexing → {}",
+                        lex.slice()
+                    ))
+                )),
+            },
             Some(token) => output.push((token, lex.span())),
             None => break,
         }
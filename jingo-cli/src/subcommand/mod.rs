@@ -1,4 +1,6 @@
 //! Subcommands to use for cli invoking
 
+pub mod compile;
+pub mod fmt;
 pub mod lex;
 pub mod parse;
@@ -1,33 +1,63 @@
 //! Parser runner
 
+use crate::diagnostic::render_diagnostic;
 use crate::utils::{help_exit, msg_exit, open_file};
 use crate::{FilePos, Parsed};
 use ansi_term::Style;
-use jingo_lib::frontend::{lexer::Token, parser};
+use jingo_lib::frontend::{lexer::Token, parser, print};
 use logos::Logos;
 use std::path::PathBuf;
 
 /// Runs parsing steps
 pub fn launch(parsed: Parsed) {
-    if parsed.data.len() == 0 {
+    let dump_spans = parsed.data.iter().any(|arg| arg == "--dump-spans");
+    let dot = parsed.data.iter().any(|arg| arg == "--dot");
+    let files: Vec<&String> = parsed
+        .data
+        .iter()
+        .filter(|arg| *arg != "--dump-spans" && *arg != "--dot")
+        .collect();
+
+    if files.is_empty() {
         help_exit("No files passed for parsing")
-    } else if parsed.data.len() > 1 {
+    } else if files.len() > 1 {
         help_exit("More then one file passed for parsing")
     }
 
-    let path = PathBuf::from(parsed.data[0].clone());
+    let path = PathBuf::from(files[0].clone());
     let input = &open_file(path.clone());
 
     let mut lex = Token::lexer(input);
 
     match parser::launch(&mut lex) {
+        Ok(parsed) if dump_spans => print!("Parsed spans ↴\n{}", print::dump_spans(&parsed)),
+        Ok(parsed) if dot => print!("{}", print::to_dot(&parsed)),
         Ok(parsed) => println!("Parsed result ↴\n{:#?}", parsed),
-        Err(err) => msg_exit(format!(
-            "Error in {}\n{}",
-            FilePos::new(path, input, lex.span().start).unwrap(),
-            Style::new()
-                .bold()
-                .paint(format!("  Found something whilst parsing → {}", err))
-        )),
+        Err(err) => {
+            let span = match (&err, lex.extras.bad_escape) {
+                (parser::ParseStop::UnterminatedString(ind), _) => *ind..*ind + 1,
+                (parser::ParseStop::DuplicateParameter(id, ind, _), _) => *ind..*ind + id.0.len(),
+                (_, Some(ind)) => ind..ind + 2,
+                (_, None) => match err.offset() {
+                    Some(ind) if ind != lex.span().start => ind..ind + 1,
+                    _ => lex.span(),
+                },
+            };
+            let secondary = match &err {
+                parser::ParseStop::DuplicateParameter(id, _, first) => {
+                    vec![(*first..*first + id.0.len(), "first defined here")]
+                }
+                _ => vec![],
+            };
+
+            msg_exit(format!(
+                "Error in {}\n{}\n{}",
+                FilePos::new(path, input, span.start).unwrap(),
+                Style::new()
+                    .bold()
+                    .paint(format!("  Found something whilst parsing → {}", err)),
+                render_diagnostic(input, (span, "found here"), secondary)
+            ))
+        }
     }
 }
@@ -0,0 +1,23 @@
+//! Benchmarks for parsing, focusing on long chains of operations which create
+//! deep expression trees
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use jingo_lib::frontend;
+
+/// Creates a chain of `terms` additions such as `1 + 1 + 1`
+fn op_chain(terms: usize) -> String {
+    vec!["1"; terms].join(" + ")
+}
+
+fn parse_chains(c: &mut Criterion) {
+    for terms in [1_000, 5_000] {
+        let input = op_chain(terms);
+
+        c.bench_function(&format!("parse {} term chain", terms), |b| {
+            b.iter(|| frontend::parse(black_box(&input)).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, parse_chains);
+criterion_main!(benches);
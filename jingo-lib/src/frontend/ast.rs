@@ -1,5 +1,7 @@
 //! Expression-centric abstract syntax tree for Jingo
 
+use std::{convert::TryFrom, fmt};
+
 /// Central expression structure, defining the fundamental structure of Jingo
 ///
 /// To parse into this structure and therefore an [ExprKind], please use the
@@ -15,17 +17,191 @@ pub struct Expr {
 
     /// Starting index of this expression
     pub start: usize,
+
+    /// Ending index of this expression, exclusive
+    pub end: usize,
 }
 
 impl Expr {
-    /// Shortcut method for getting from parsing
+    /// Shortcut method for getting from parsing, with the end being the same
+    /// as the start until the parser finishes the expression
     pub(crate) fn from_parse(kind: impl Into<ExprKind>, doc: Option<String>, start: usize) -> Self {
         Self {
             kind: kind.into(),
             doc,
             start,
+            end: start,
+        }
+    }
+
+    /// Gets the direct child expressions of this expression in source order,
+    /// allowing simple recursive traversal of the tree
+    pub fn children(&self) -> Vec<&Expr> {
+        match &self.kind {
+            ExprKind::Body(Body(exprs)) | ExprKind::Interpolation(Interpolation(exprs)) => {
+                exprs.iter().collect()
+            }
+            ExprKind::Not(Not(expr))
+            | ExprKind::Neg(Neg(expr))
+            | ExprKind::Return(Return(expr)) => vec![expr],
+            ExprKind::Op(op) => vec![&op.left, &op.right],
+            ExprKind::Class(class) => class
+                .constructor
+                .iter()
+                .flat_map(|constructor| constructor.body.iter())
+                .chain(class.body.iter())
+                .collect(),
+            ExprKind::Function(function) => function.body.iter().collect(),
+            ExprKind::Method(method) => method.body.iter().collect(),
+            ExprKind::FunctionCall(call) => call.args.iter().collect(),
+            ExprKind::Match(matched) => {
+                let mut found = vec![matched.condition.as_ref()];
+
+                for seg in matched.segments.iter() {
+                    if let Pattern::Expr(expr) = &seg.pattern {
+                        found.push(expr);
+                    }
+
+                    found.push(&seg.expr);
+                }
+
+                found
+            }
+            ExprKind::If(cond) => {
+                let mut found = vec![];
+
+                for seg in cond.segments.iter() {
+                    found.push(seg.condition.as_ref());
+                    found.extend(seg.body.iter());
+                }
+
+                if let Some(IfDefault(body)) = &cond.default {
+                    found.extend(body.iter());
+                }
+
+                found
+            }
+            ExprKind::While(looped) => {
+                let mut found = vec![looped.condition.as_ref()];
+                found.extend(looped.body.iter());
+                found
+            }
+            ExprKind::Let(assigned) => assigned.expr.iter().map(|expr| &**expr).collect(),
+            ExprKind::LetSet(set) => vec![&set.expr],
+            ExprKind::CondLet(cond) => vec![&cond.expr],
+            ExprKind::Cast(cast) => vec![&cast.expr],
+            ExprKind::Path(_)
+            | ExprKind::LetCall(_)
+            | ExprKind::Import(_)
+            | ExprKind::IntLit(_)
+            | ExprKind::FloatLit(_)
+            | ExprKind::StrLit(_)
+            | ExprKind::CharLit(_)
+            | ExprKind::BoolLit(_)
+            | ExprKind::Break
+            | ExprKind::None => vec![],
+        }
+    }
+
+    /// Applies `f` to each direct child expression of this expression in place
+    /// and in source order, allowing the tree to be rewritten without cloning
+    pub fn map_children(&mut self, mut f: impl FnMut(&mut Expr)) {
+        match &mut self.kind {
+            ExprKind::Body(Body(exprs)) | ExprKind::Interpolation(Interpolation(exprs)) => {
+                exprs.iter_mut().for_each(f)
+            }
+            ExprKind::Not(Not(expr))
+            | ExprKind::Neg(Neg(expr))
+            | ExprKind::Return(Return(expr)) => f(expr),
+            ExprKind::Op(op) => {
+                f(&mut op.left);
+                f(&mut op.right);
+            }
+            ExprKind::Class(class) => {
+                if let Some(constructor) = &mut class.constructor {
+                    constructor.body.iter_mut().for_each(&mut f);
+                }
+
+                class.body.iter_mut().for_each(f)
+            }
+            ExprKind::Function(function) => function.body.iter_mut().for_each(f),
+            ExprKind::Method(method) => method.body.iter_mut().for_each(f),
+            ExprKind::FunctionCall(call) => call.args.iter_mut().for_each(f),
+            ExprKind::Match(matched) => {
+                f(&mut matched.condition);
+
+                for seg in matched.segments.iter_mut() {
+                    if let Pattern::Expr(expr) = &mut seg.pattern {
+                        f(expr);
+                    }
+
+                    f(&mut seg.expr);
+                }
+            }
+            ExprKind::If(cond) => {
+                for seg in cond.segments.iter_mut() {
+                    f(&mut seg.condition);
+                    seg.body.iter_mut().for_each(&mut f);
+                }
+
+                if let Some(IfDefault(body)) = &mut cond.default {
+                    body.iter_mut().for_each(f);
+                }
+            }
+            ExprKind::While(looped) => {
+                f(&mut looped.condition);
+                looped.body.iter_mut().for_each(f);
+            }
+            ExprKind::Let(assigned) => assigned.expr.iter_mut().for_each(|expr| f(expr)),
+            ExprKind::LetSet(set) => f(&mut set.expr),
+            ExprKind::CondLet(cond) => f(&mut cond.expr),
+            ExprKind::Cast(cast) => f(&mut cast.expr),
+            ExprKind::Path(_)
+            | ExprKind::LetCall(_)
+            | ExprKind::Import(_)
+            | ExprKind::IntLit(_)
+            | ExprKind::FloatLit(_)
+            | ExprKind::StrLit(_)
+            | ExprKind::CharLit(_)
+            | ExprKind::BoolLit(_)
+            | ExprKind::Break
+            | ExprKind::None => (),
         }
     }
+
+    /// Checks if this expression is equal to `other` whilst treating the
+    /// operands of commutative operations such as `a + b` and `b + a` as equal,
+    /// recursively. Source positions are ignored
+    pub fn eq_commutative(&self, other: &Expr) -> bool {
+        if let (ExprKind::Op(left), ExprKind::Op(right)) = (&self.kind, &other.kind) {
+            return left.kind == right.kind
+                && self.doc == other.doc
+                && ((left.left.eq_commutative(&right.left)
+                    && left.right.eq_commutative(&right.right))
+                    || (left.kind.is_commutative()
+                        && left.left.eq_commutative(&right.right)
+                        && left.right.eq_commutative(&right.left)));
+        }
+
+        self.shallow() == other.shallow()
+            && self
+                .children()
+                .iter()
+                .zip(other.children())
+                .all(|(left, right)| left.eq_commutative(right))
+    }
+
+    /// Clones this expression without its position and with every child
+    /// replaced by a `none`, used for comparing a single node
+    fn shallow(&self) -> Expr {
+        let mut cloned = self.clone();
+
+        cloned.start = 0;
+        cloned.end = 0;
+        cloned.map_children(|child| *child = Expr::from_parse(ExprKind::None, None, 0));
+
+        cloned
+    }
 }
 
 /// Expression kind enumeration for the AST, containing all possible variants for
@@ -34,6 +210,7 @@ impl Expr {
 pub enum ExprKind {
     Body(Body),
     Not(Not),
+    Neg(Neg),
     Op(Op),
     Path(Path),
     Class(Class),
@@ -41,24 +218,102 @@ pub enum ExprKind {
     Method(Method),
     FunctionCall(FunctionCall),
     Match(Match),
+    If(If),
     While(While),
     Return(Return),
     Let(Let),
     LetSet(LetSet),
     LetCall(LetCall),
+    Import(Import),
+    CondLet(CondLet),
+    Cast(Cast),
     IntLit(IntLit),
     FloatLit(FloatLit),
     StrLit(StrLit),
+    Interpolation(Interpolation),
     CharLit(CharLit),
     BoolLit(BoolLit),
     Break,
     None,
 }
 
-/// Body of `( <exprs> )` allowing expansion from a single expression to multiple
+impl ExprKind {
+    /// Checks if this kind is a literal value, not including `none`
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            ExprKind::IntLit(_)
+                | ExprKind::FloatLit(_)
+                | ExprKind::StrLit(_)
+                | ExprKind::CharLit(_)
+                | ExprKind::BoolLit(_)
+        )
+    }
+
+    /// Checks if this kind is a declaration which documentation can be attached
+    /// to, being a function, method, class or let
+    pub fn is_declaration(&self) -> bool {
+        matches!(
+            self,
+            ExprKind::Function(_) | ExprKind::Method(_) | ExprKind::Class(_) | ExprKind::Let(_)
+        )
+    }
+
+    /// Gets the name of this kind's variant, used for displaying
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExprKind::Body(_) => "Body",
+            ExprKind::Not(_) => "Not",
+            ExprKind::Neg(_) => "Neg",
+            ExprKind::Op(_) => "Op",
+            ExprKind::Path(_) => "Path",
+            ExprKind::Class(_) => "Class",
+            ExprKind::Function(_) => "Function",
+            ExprKind::Method(_) => "Method",
+            ExprKind::FunctionCall(_) => "FunctionCall",
+            ExprKind::Match(_) => "Match",
+            ExprKind::If(_) => "If",
+            ExprKind::While(_) => "While",
+            ExprKind::Return(_) => "Return",
+            ExprKind::Let(_) => "Let",
+            ExprKind::LetSet(_) => "LetSet",
+            ExprKind::LetCall(_) => "LetCall",
+            ExprKind::Import(_) => "Import",
+            ExprKind::CondLet(_) => "CondLet",
+            ExprKind::Cast(_) => "Cast",
+            ExprKind::IntLit(_) => "IntLit",
+            ExprKind::FloatLit(_) => "FloatLit",
+            ExprKind::StrLit(_) => "StrLit",
+            ExprKind::Interpolation(_) => "Interpolation",
+            ExprKind::CharLit(_) => "CharLit",
+            ExprKind::BoolLit(_) => "BoolLit",
+            ExprKind::Break => "Break",
+            ExprKind::None => "None",
+        }
+    }
+}
+
+/// Braced body of `{ <exprs> }` used where a single expression may expand to
+/// multiple, such as the arms of a [Match]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Body(pub Vec<Expr>);
 
+impl Body {
+    /// Wraps a `body` of expressions into a single [Body] expression, spanning
+    /// from the start of the first expression to the end of the last
+    pub fn from_body(body: Vec<Expr>) -> Expr {
+        let start = body.first().map_or(0, |expr| expr.start);
+        let end = body.last().map_or(start, |expr| expr.end);
+
+        Expr {
+            kind: Body(body).into(),
+            doc: None,
+            start,
+            end,
+        }
+    }
+}
+
 impl From<Body> for ExprKind {
     fn from(kind: Body) -> Self {
         ExprKind::Body(kind)
@@ -81,6 +336,16 @@ impl From<Not> for ExprKind {
     }
 }
 
+/// Right-associative negation symbol, i.e. `-x`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Neg(pub Box<Expr>);
+
+impl From<Neg> for ExprKind {
+    fn from(kind: Neg) -> Self {
+        ExprKind::Neg(kind)
+    }
+}
+
 /// Binary operation allowing two [Expr]s to be modified by a mathematical notation
 #[derive(Debug, Clone, PartialEq)]
 pub struct Op {
@@ -92,6 +357,9 @@ pub struct Op {
 
     /// Mathematical notation to modify [Op::left] and [Op::right] together by
     pub kind: OpKind,
+
+    /// If this operation was wrapped in its own parenthesis, such as `(1 + 2)`
+    pub grouped: bool,
 }
 
 impl From<Op> for ExprKind {
@@ -100,13 +368,41 @@ impl From<Op> for ExprKind {
     }
 }
 
+/// Flattens a chain of the same associative operation into its operands in
+/// order, i.e. `1 + 2 + 3` into `(Plus, [1, 2, 3])`, or gives [None] if the
+/// operation isn't associative. Nested operations of a different kind are kept
+/// as single operands
+pub fn flatten_chain(op: &Op) -> Option<(OpKind, Vec<Expr>)> {
+    fn flatten_into(expr: &Expr, kind: OpKind, operands: &mut Vec<Expr>) {
+        match &expr.kind {
+            ExprKind::Op(op) if op.kind == kind => {
+                flatten_into(&op.left, kind, operands);
+                flatten_into(&op.right, kind, operands);
+            }
+            _ => operands.push(expr.clone()),
+        }
+    }
+
+    if !op.kind.is_associative() {
+        return None;
+    }
+
+    let mut operands = vec![];
+    flatten_into(&op.left, op.kind, &mut operands);
+    flatten_into(&op.right, op.kind, &mut operands);
+
+    Some((op.kind, operands))
+}
+
 /// Binary operation variants, defining allowed types of a [Op] expression
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OpKind {
     Plus,
     Sub,
     Mul,
     Div,
+    FloorDiv,
+    Pow,
     Greater,
     GreaterEq,
     Less,
@@ -119,8 +415,170 @@ pub enum OpKind {
     SubEq,
 }
 
+impl OpKind {
+    /// Checks if this operation compares its operands, giving a boolean
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            OpKind::Greater
+                | OpKind::GreaterEq
+                | OpKind::Less
+                | OpKind::LessEq
+                | OpKind::EqEq
+                | OpKind::NotEq
+        )
+    }
+
+    /// Gets the binding power of this operation, where a higher precedence binds
+    /// tighter, so `**` binds tighter than `*` and `/`, which bind tighter than
+    /// `+` and `-`, which bind tighter than comparisons, then `and` and finally `or`
+    pub fn precedence(&self) -> u8 {
+        match self {
+            OpKind::Pow => 6,
+            OpKind::Mul | OpKind::Div | OpKind::FloorDiv => 5,
+            OpKind::Plus | OpKind::Sub => 4,
+            OpKind::Greater
+            | OpKind::GreaterEq
+            | OpKind::Less
+            | OpKind::LessEq
+            | OpKind::EqEq
+            | OpKind::NotEq => 3,
+            OpKind::And => 2,
+            OpKind::Or => 1,
+            OpKind::PlusEq | OpKind::SubEq => 0,
+        }
+    }
+
+    /// Checks if the order of operands doesn't change the result of this
+    /// operation, i.e. `a + b` being the same as `b + a`
+    pub fn is_commutative(&self) -> bool {
+        matches!(
+            self,
+            OpKind::Plus | OpKind::Mul | OpKind::And | OpKind::Or | OpKind::EqEq | OpKind::NotEq
+        )
+    }
+
+    /// Checks if chained operations of this kind group from the right, i.e.
+    /// `2 ** 3 ** 2` being `2 ** (3 ** 2)`, rather than from the left
+    pub fn is_right_associative(&self) -> bool {
+        matches!(self, OpKind::Pow)
+    }
+
+    /// Checks if chaining this operation gives the same result however it's
+    /// grouped, i.e. `(a + b) + c` being the same as `a + (b + c)`
+    pub fn is_associative(&self) -> bool {
+        matches!(self, OpKind::Plus | OpKind::Mul | OpKind::And | OpKind::Or)
+    }
+
+    /// Evaluates this operation over two literal operands, returning the
+    /// resulting literal. This gives [None] if the operands are not literals,
+    /// their types don't match this operation or for division by zero
+    ///
+    /// Division using `/` always gives a float, even for two integers, whereas
+    /// floor division using `//` keeps the type of its operands
+    pub fn evaluate(&self, left: &ExprKind, right: &ExprKind) -> Option<ExprKind> {
+        use ExprKind::*;
+
+        Some(match (left, right) {
+            (IntLit(self::IntLit(l, _)), IntLit(self::IntLit(r, _))) => match self {
+                OpKind::Plus => self::IntLit::new(l.checked_add(*r)?).into(),
+                OpKind::Sub => self::IntLit::new(l.checked_sub(*r)?).into(),
+                OpKind::Mul => self::IntLit::new(l.checked_mul(*r)?).into(),
+                OpKind::Div if *r == 0 => return Option::None,
+                OpKind::Div => self::FloatLit(*l as f64 / *r as f64).into(),
+                OpKind::Pow => self::IntLit::new(l.checked_pow(u32::try_from(*r).ok()?)?).into(),
+                OpKind::FloorDiv => {
+                    let quotient = l.checked_div(*r)?;
+
+                    if l % r != 0 && (*l < 0) != (*r < 0) {
+                        self::IntLit::new(quotient - 1).into()
+                    } else {
+                        self::IntLit::new(quotient).into()
+                    }
+                }
+                _ => self::BoolLit(self.compare(l, r)?).into(),
+            },
+            (FloatLit(self::FloatLit(l)), FloatLit(self::FloatLit(r))) => match self {
+                OpKind::Plus => self::FloatLit(l + r).into(),
+                OpKind::Sub => self::FloatLit(l - r).into(),
+                OpKind::Mul => self::FloatLit(l * r).into(),
+                OpKind::Div | OpKind::FloorDiv if *r == 0.0 => return Option::None,
+                OpKind::Div => self::FloatLit(l / r).into(),
+                OpKind::Pow => self::FloatLit(l.powf(*r)).into(),
+                OpKind::FloorDiv => self::FloatLit((l / r).floor()).into(),
+                _ => self::BoolLit(self.compare(l, r)?).into(),
+            },
+            (StrLit(self::StrLit(l)), StrLit(self::StrLit(r))) => match self {
+                OpKind::Plus => self::StrLit(format!("{}{}", l, r)).into(),
+                OpKind::EqEq | OpKind::NotEq => self::BoolLit(self.compare(l, r)?).into(),
+                _ => return Option::None,
+            },
+            (CharLit(self::CharLit(l)), CharLit(self::CharLit(r))) => {
+                self::BoolLit(self.compare(l, r)?).into()
+            }
+            (BoolLit(self::BoolLit(l)), BoolLit(self::BoolLit(r))) => match self {
+                OpKind::And => self::BoolLit(*l && *r).into(),
+                OpKind::Or => self::BoolLit(*l || *r).into(),
+                OpKind::EqEq | OpKind::NotEq => self::BoolLit(self.compare(l, r)?).into(),
+                _ => return Option::None,
+            },
+            (None, None) => match self {
+                OpKind::EqEq => self::BoolLit(true).into(),
+                OpKind::NotEq => self::BoolLit(false).into(),
+                _ => return Option::None,
+            },
+            (None, other) | (other, None) if other.is_literal() => match self {
+                OpKind::EqEq => self::BoolLit(false).into(),
+                OpKind::NotEq => self::BoolLit(true).into(),
+                _ => return Option::None,
+            },
+            _ => return Option::None,
+        })
+    }
+
+    /// Compares two values if this is a comparison operation
+    fn compare<T: PartialOrd>(&self, left: T, right: T) -> Option<bool> {
+        match self {
+            OpKind::Greater => Some(left > right),
+            OpKind::GreaterEq => Some(left >= right),
+            OpKind::Less => Some(left < right),
+            OpKind::LessEq => Some(left <= right),
+            OpKind::EqEq => Some(left == right),
+            OpKind::NotEq => Some(left != right),
+            _ => Option::None,
+        }
+    }
+}
+
+impl fmt::Display for OpKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                OpKind::Plus => "+",
+                OpKind::Sub => "-",
+                OpKind::Mul => "*",
+                OpKind::Div => "/",
+                OpKind::FloorDiv => "//",
+                OpKind::Pow => "**",
+                OpKind::Greater => ">",
+                OpKind::GreaterEq => ">=",
+                OpKind::Less => "<",
+                OpKind::LessEq => "<=",
+                OpKind::EqEq => "==",
+                OpKind::NotEq => "!=",
+                OpKind::And => "and",
+                OpKind::Or => "or",
+                OpKind::PlusEq => "+=",
+                OpKind::SubEq => "-=",
+            }
+        )
+    }
+}
+
 /// Pre-validated valid identifier
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Id(pub String);
 
 impl From<String> for Id {
@@ -135,8 +593,14 @@ impl From<&str> for Id {
     }
 }
 
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Path to a node, with fields before separated with `.` towards a final identifier
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Path {
     /// Fields before the final identifier
     pub fields: Vec<Id>,
@@ -164,12 +628,42 @@ impl Path {
 
     /// Converts to the single [Path::id] provided there are no other fields and its not affixed
     pub fn to_id(self) -> Option<Id> {
-        if self.fields.len() != 0 || self.affixed {
+        if !self.fields.is_empty() || self.affixed {
             None
         } else {
             Some(self.id)
         }
     }
+
+    /// Gets a copy of this path with its leading dot, i.e. `a.b` into `.a.b`
+    pub fn with_affix(&self) -> Path {
+        Self {
+            affixed: true,
+            ..self.clone()
+        }
+    }
+
+    /// Gets a copy of this path without its leading dot, i.e. `.a.b` into `a.b`
+    pub fn without_affix(&self) -> Path {
+        Self {
+            affixed: false,
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.affixed {
+            write!(f, ".")?;
+        }
+
+        for field in self.fields.iter() {
+            write!(f, "{}.", field)?;
+        }
+
+        write!(f, "{}", self.id)
+    }
 }
 
 impl From<Path> for ExprKind {
@@ -178,12 +672,33 @@ impl From<Path> for ExprKind {
     }
 }
 
+/// Visibility of a declaration from outside of its module, given by a leading
+/// `pub` keyword
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+    Public,
+    #[default]
+    Private,
+}
+
 /// Class definition
 #[derive(Debug, Clone, PartialEq)]
 pub struct Class {
+    /// Visibility of class
+    pub visibility: Visibility,
+
     /// Name of class
     pub id: Id,
 
+    /// Parent class this inherits from, given after a `:` such as `class Dog : Animal`
+    pub parent: Option<Path>,
+
+    /// Constructor of class, taken from the `init` method in its body
+    pub constructor: Option<Method>,
+
+    /// Documentation of the constructor, as it isn't kept in an [Expr]
+    pub constructor_doc: Option<String>,
+
     /// Body of class, specially parsed further downstream
     pub body: Vec<Expr>,
 }
@@ -199,6 +714,9 @@ impl From<Class> for ExprKind {
 /// non-class-linked subprograms
 #[derive(Debug, Clone, PartialEq)]
 pub struct Function {
+    /// Visibility of function
+    pub visibility: Visibility,
+
     /// Path to the relevant node information
     pub path: Path,
 
@@ -216,9 +734,13 @@ impl From<Function> for ExprKind {
 }
 
 /// Class-linked subprogram similar to the base [Function], but is strictly linked
-/// to a certain class
+/// to a certain class. Methods taking `self` as their first argument are called
+/// on instances of the class, otherwise they're called on the class itself
 #[derive(Debug, Clone, PartialEq)]
 pub struct Method {
+    /// Visibility of method
+    pub visibility: Visibility,
+
     /// Path to the relevant node information
     pub path: Path,
 
@@ -229,6 +751,25 @@ pub struct Method {
     pub body: Vec<Expr>,
 }
 
+impl From<Function> for Method {
+    fn from(function: Function) -> Self {
+        Self {
+            visibility: function.visibility,
+            path: function.path,
+            args: function.args,
+            body: function.body,
+        }
+    }
+}
+
+impl Method {
+    /// Checks if this method takes `self` as its first argument, meaning it's
+    /// called on an instance of its class
+    pub fn takes_self(&self) -> bool {
+        self.args.first().is_some_and(|arg| arg.0 == "self")
+    }
+}
+
 impl From<Method> for ExprKind {
     fn from(kind: Method) -> Self {
         ExprKind::Method(kind)
@@ -251,11 +792,21 @@ impl From<FunctionCall> for ExprKind {
     }
 }
 
+/// Pattern for a [MatchSegment] to match against
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Expression to equate with the condition of a [Match]
+    Expr(Box<Expr>),
+
+    /// Catch-all `_` pattern which always matches
+    Wildcard,
+}
+
 /// Segment of [Match] which contains a branch
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchSegment {
-    /// Expression to match
-    pub condition: Box<Expr>,
+    /// Pattern to match
+    pub pattern: Pattern,
 
     /// Multiple expression body to run if the match succeeds
     pub expr: Box<Expr>,
@@ -272,9 +823,22 @@ pub struct Match {
 
     /// Each segment to try to equate and run
     pub segments: Vec<MatchSegment>,
+}
 
-    /// Default expression to use if none matched
-    pub default: Option<Box<Expr>>,
+impl Match {
+    /// Gets the segments which can never be reached due to being after a
+    /// [Pattern::Wildcard] segment. The parser allows these, with the warning for
+    /// them given by [find_unreachable_arms](crate::frontend::lint::find_unreachable_arms)
+    pub fn unreachable_segments(&self) -> &[MatchSegment] {
+        match self
+            .segments
+            .iter()
+            .position(|seg| seg.pattern == Pattern::Wildcard)
+        {
+            Some(ind) => &self.segments[ind + 1..],
+            None => &[],
+        }
+    }
 }
 
 impl From<Match> for ExprKind {
@@ -283,6 +847,59 @@ impl From<Match> for ExprKind {
     }
 }
 
+/// Segment of [If] which contains a condition and the body to run if it's true
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfSegment {
+    /// Condition needed in order to run
+    pub condition: Box<Expr>,
+
+    /// Body of segment
+    pub body: Vec<Expr>,
+}
+
+/// Default `else` body of [If] which is ran if no segments were
+#[derive(Debug, Clone, PartialEq)]
+pub struct IfDefault(pub Vec<Expr>);
+
+/// If expression, running the first segment whose condition is true or the
+/// default otherwise. As an expression, this yields the value of the tail
+/// (last) expression of whichever body was ran, which is `none` when no
+/// segment runs and there's no default, as given by [If::default_body]
+#[derive(Debug, Clone, PartialEq)]
+pub struct If {
+    /// Segments for the initial `if` and each following `else if`
+    pub segments: Vec<IfSegment>,
+
+    /// Optional default `else` body
+    pub default: Option<IfDefault>,
+}
+
+/// Implicit body of an [If] without a default, yielding `none`
+static IMPLICIT_DEFAULT: Expr = Expr {
+    kind: ExprKind::None,
+    doc: None,
+    start: 0,
+    end: 0,
+};
+
+impl If {
+    /// Gets the body which is ran if no segments are, being the `else` body or
+    /// an implicit `none` if there isn't one so that `let x = if c { 1 }` sets
+    /// `x` to `none` when `c` is false
+    pub fn default_body(&self) -> &[Expr] {
+        match &self.default {
+            Some(IfDefault(body)) => body,
+            None => std::slice::from_ref(&IMPLICIT_DEFAULT),
+        }
+    }
+}
+
+impl From<If> for ExprKind {
+    fn from(kind: If) -> Self {
+        ExprKind::If(kind)
+    }
+}
+
 /// While loop, requiring a condition in order to fire the body repeatedly
 #[derive(Debug, Clone, PartialEq)]
 pub struct While {
@@ -314,14 +931,18 @@ impl From<Return> for ExprKind {
 /// [Let::mutable] is [true]
 #[derive(Debug, Clone, PartialEq)]
 pub struct Let {
+    /// Visibility of let
+    pub visibility: Visibility,
+
     /// Path to the relevant node information
     pub path: Path,
 
     /// Determines if this Let is mutable
     pub mutable: bool,
 
-    /// Expression which determines initial Let state
-    pub expr: Box<Expr>,
+    /// Expression which determines initial Let state, if it isn't deferred such
+    /// as with `let x`
+    pub expr: Option<Box<Expr>>,
 }
 
 impl From<Let> for ExprKind {
@@ -335,7 +956,7 @@ pub struct LetCall(pub Path);
 
 impl From<Path> for LetCall {
     fn from(path: Path) -> Self {
-        Self(path.into())
+        Self(path)
     }
 }
 
@@ -345,6 +966,17 @@ impl From<LetCall> for ExprKind {
     }
 }
 
+/// Import of a declaration from another module, i.e. `import a.b` bringing in
+/// `b` from the module `a`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import(pub Path);
+
+impl From<Import> for ExprKind {
+    fn from(kind: Import) -> Self {
+        ExprKind::Import(kind)
+    }
+}
+
 /// Let setter for overwriting data in an existing [Let] whilst
 /// [Let::mutable] is [true]
 #[derive(Debug, Clone, PartialEq)]
@@ -362,9 +994,51 @@ impl From<LetSet> for ExprKind {
     }
 }
 
-/// Integer literal used for defining raw integers
+/// Conditional binding used as the condition of an [If] or [While], binding the
+/// result of [CondLet::expr] to [CondLet::binding] and testing its truthiness,
+/// i.e. `if let x = expr {}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CondLet {
+    /// Path the expression is bound to
+    pub binding: Path,
+
+    /// Expression which is bound and tested
+    pub expr: Box<Expr>,
+}
+
+impl From<CondLet> for ExprKind {
+    fn from(kind: CondLet) -> Self {
+        ExprKind::CondLet(kind)
+    }
+}
+
+/// Explicit conversion of an expression into another type, i.e. `x as Int`
 #[derive(Debug, Clone, PartialEq)]
-pub struct IntLit(pub i64);
+pub struct Cast {
+    /// Expression being converted
+    pub expr: Box<Expr>,
+
+    /// Path to the type being converted to
+    pub ty: Path,
+}
+
+impl From<Cast> for ExprKind {
+    fn from(kind: Cast) -> Self {
+        ExprKind::Cast(kind)
+    }
+}
+
+/// Integer literal used for defining raw integers, along with the radix it was
+/// written in so it can be printed back the same way
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntLit(pub i64, pub Radix);
+
+impl IntLit {
+    /// Creates a new decimal integer literal
+    pub fn new(int: i64) -> Self {
+        Self(int, Radix::Decimal)
+    }
+}
 
 impl From<IntLit> for ExprKind {
     fn from(kind: IntLit) -> Self {
@@ -372,10 +1046,44 @@ impl From<IntLit> for ExprKind {
     }
 }
 
+/// Radix an [IntLit] was written in, given by its `0x`, `0b` or `0o` prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Radix {
+    #[default]
+    Decimal,
+    Hex,
+    Binary,
+    Octal,
+}
+
+impl Radix {
+    /// Gets the radix of an integer from the source text it was lexed from
+    pub fn of(text: &str) -> Self {
+        match text.get(..2) {
+            Some("0x") => Radix::Hex,
+            Some("0b") => Radix::Binary,
+            Some("0o") => Radix::Octal,
+            _ => Radix::Decimal,
+        }
+    }
+}
+
 /// Float literal used for defining raw floats
 #[derive(Debug, Clone, PartialEq)]
 pub struct FloatLit(pub f64);
 
+impl FloatLit {
+    /// Gets the raw bit representation of this float, which unlike [f64] can be
+    /// used as a key for hashing, i.e. deduplicating a constant pool
+    ///
+    /// This compares bits and not values, so `0.0` and `-0.0` give different
+    /// keys despite being equal. NaNs may give different keys depending on
+    /// their payload, so a NaN is only guaranteed to match the same NaN
+    pub fn to_bits_key(&self) -> u64 {
+        self.0.to_bits()
+    }
+}
+
 impl From<FloatLit> for ExprKind {
     fn from(kind: FloatLit) -> Self {
         ExprKind::FloatLit(kind)
@@ -392,6 +1100,17 @@ impl From<StrLit> for ExprKind {
     }
 }
 
+/// Interpolated string such as `"hello ${name}"`, made up of the [StrLit] parts
+/// and the embedded expressions between them in the order they're concatenated
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpolation(pub Vec<Expr>);
+
+impl From<Interpolation> for ExprKind {
+    fn from(kind: Interpolation) -> Self {
+        ExprKind::Interpolation(kind)
+    }
+}
+
 /// Char literal used for defining raw chars
 #[derive(Debug, Clone, PartialEq)]
 pub struct CharLit(pub u32);
@@ -411,3 +1130,314 @@ impl From<BoolLit> for ExprKind {
         ExprKind::BoolLit(kind)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::parse;
+
+    #[test]
+    fn children() {
+        let parsed = parse("5 + 3").unwrap();
+        let children = parsed[0].children();
+
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].kind, IntLit::new(5).into());
+        assert_eq!(children[1].kind, IntLit::new(3).into());
+
+        let parsed = parse("while x { 1 2 }").unwrap();
+        let kinds: Vec<&ExprKind> = parsed[0].children().iter().map(|c| &c.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                &LetCall(Path::new("x")).into(),
+                &IntLit::new(1).into(),
+                &IntLit::new(2).into()
+            ]
+        );
+        assert!(parse("5").unwrap()[0].children().is_empty());
+    }
+
+    #[test]
+    fn map_children() {
+        fn double(expr: &mut Expr) {
+            if let ExprKind::IntLit(IntLit(int, _)) = &mut expr.kind {
+                *int *= 2;
+            }
+
+            expr.map_children(double);
+        }
+
+        let mut parsed = parse("while 1 + 2 { if 3 { 4 } else { (1 + 2) } let x = !3 }").unwrap();
+        parsed.iter_mut().for_each(double);
+
+        assert_eq!(
+            parsed,
+            parse("while 2 + 4 { if 6 { 8 } else { (2 + 4) } let x = !6 }").unwrap()
+        );
+    }
+
+    #[test]
+    fn body_wrapping() {
+        let body = parse("let x = 5\nx + 1").unwrap();
+        let wrapped = Body::from_body(body.clone());
+
+        assert_eq!((wrapped.start, wrapped.end), (0, 15));
+        assert_eq!(wrapped.kind, ExprKind::Body(Body(body)));
+        assert_eq!(wrapped.children().len(), 2);
+        assert_eq!(Body::from_body(vec![]).kind, ExprKind::Body(Body(vec![])));
+    }
+
+    #[test]
+    fn chain_flattening() {
+        let flattened = |input| match parse(input).unwrap().remove(0).kind {
+            ExprKind::Op(op) => flatten_chain(&op).map(|(kind, operands)| {
+                (
+                    kind,
+                    operands
+                        .into_iter()
+                        .map(|operand| operand.kind)
+                        .collect::<Vec<_>>(),
+                )
+            }),
+            other => panic!("expected op, found {:?}", other),
+        };
+
+        assert_eq!(
+            flattened("1 + 2 + 3"),
+            Some((
+                OpKind::Plus,
+                vec![
+                    IntLit::new(1).into(),
+                    IntLit::new(2).into(),
+                    IntLit::new(3).into()
+                ]
+            ))
+        );
+        assert_eq!(flattened("1 * (2 * 3) * 4").unwrap().1.len(), 4);
+        assert_eq!(flattened("1 - 2 - 3"), None);
+
+        let (kind, operands) = flattened("1 + 2 * 3 + 4").unwrap();
+
+        assert_eq!(kind, OpKind::Plus);
+        assert_eq!(operands.len(), 3);
+        assert_eq!(operands[1].name(), "Op");
+    }
+
+    #[test]
+    fn if_defaults() {
+        let parsed = parse("let x = if c { 1 }").unwrap();
+        let cond = match &parsed[0].kind {
+            ExprKind::Let(Let {
+                expr: Some(expr), ..
+            }) => match &expr.kind {
+                ExprKind::If(cond) => cond,
+                other => panic!("expected if, found {:?}", other),
+            },
+            other => panic!("expected let, found {:?}", other),
+        };
+
+        assert_eq!(cond.default, None);
+        assert_eq!(cond.default_body().len(), 1);
+        assert_eq!(cond.default_body()[0].kind, ExprKind::None);
+
+        match &parse("if c { 1 } else { 2 }").unwrap()[0].kind {
+            ExprKind::If(cond) => assert_eq!(cond.default_body()[0].kind, IntLit::new(2).into()),
+            other => panic!("expected if, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn path_affixes() {
+        let affixed = Path {
+            fields: vec!["a".into()],
+            id: "b".into(),
+            affixed: true,
+        };
+        let plain = Path {
+            affixed: false,
+            ..affixed.clone()
+        };
+
+        assert_eq!(affixed.without_affix(), plain);
+        assert_eq!(plain.with_affix(), affixed);
+        assert_eq!(affixed.with_affix(), affixed);
+        assert_eq!(plain.without_affix(), plain);
+        assert_eq!(plain.with_affix().to_string(), ".a.b");
+        assert_eq!(affixed.without_affix().to_string(), "a.b");
+    }
+
+    #[test]
+    fn commutative_equality() {
+        let expr = |input: &str| parse(input).unwrap().remove(0);
+        let sub = |left, right| Expr {
+            kind: Op {
+                left: Box::new(Expr::from_parse(IntLit::new(left), None, 0)),
+                right: Box::new(Expr::from_parse(IntLit::new(right), None, 4)),
+                kind: OpKind::Sub,
+                grouped: false,
+            }
+            .into(),
+            doc: None,
+            start: 2,
+            end: 5,
+        };
+
+        assert!(expr("1 + 2").eq_commutative(&expr("2 + 1")));
+        assert!(expr("a and b").eq_commutative(&expr("b and a")));
+        assert!(expr("x == (1 + 2)").eq_commutative(&expr("(2 + 1) == x")));
+        assert!(expr("while a + b { c == d }").eq_commutative(&expr("while b + a { d == c }")));
+        assert!(sub(1, 2).eq_commutative(&sub(1, 2)));
+        assert!(!sub(1, 2).eq_commutative(&sub(2, 1)));
+        assert!(!expr("1 / 2").eq_commutative(&expr("2 / 1")));
+        assert!(!expr("1 + 2").eq_commutative(&expr("1 + 3")));
+        assert!(!expr("while a {}").eq_commutative(&expr("while a { b }")));
+    }
+
+    #[test]
+    fn float_keys() {
+        assert_eq!(FloatLit(2.5).to_bits_key(), FloatLit(2.5).to_bits_key());
+        assert_ne!(FloatLit(2.5).to_bits_key(), FloatLit(2.0).to_bits_key());
+        assert_ne!(FloatLit(0.0).to_bits_key(), FloatLit(-0.0).to_bits_key());
+        assert_eq!(
+            FloatLit(f64::NAN).to_bits_key(),
+            FloatLit(f64::NAN).to_bits_key()
+        );
+    }
+
+    #[test]
+    fn evaluate_ints() {
+        assert_eq!(
+            OpKind::Plus.evaluate(&IntLit::new(2).into(), &IntLit::new(3).into()),
+            Some(IntLit::new(5).into())
+        );
+        assert_eq!(
+            OpKind::Div.evaluate(&IntLit::new(5).into(), &IntLit::new(2).into()),
+            Some(FloatLit(2.5).into())
+        );
+        assert_eq!(
+            OpKind::FloorDiv.evaluate(&IntLit::new(5).into(), &IntLit::new(2).into()),
+            Some(IntLit::new(2).into())
+        );
+        assert_eq!(
+            OpKind::FloorDiv.evaluate(&IntLit::new(-5).into(), &IntLit::new(2).into()),
+            Some(IntLit::new(-3).into())
+        );
+        assert_eq!(
+            OpKind::FloorDiv.evaluate(&IntLit::new(7).into(), &IntLit::new(0).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::GreaterEq.evaluate(&IntLit::new(2).into(), &IntLit::new(3).into()),
+            Some(BoolLit(false).into())
+        );
+        assert_eq!(
+            OpKind::Div.evaluate(&IntLit::new(7).into(), &IntLit::new(0).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::Plus.evaluate(&IntLit::new(i64::MAX).into(), &IntLit::new(1).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::Pow.evaluate(&IntLit::new(2).into(), &IntLit::new(10).into()),
+            Some(IntLit::new(1024).into())
+        );
+        assert_eq!(
+            OpKind::Pow.evaluate(&IntLit::new(2).into(), &IntLit::new(-1).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::Pow.evaluate(&FloatLit(4.0).into(), &FloatLit(0.5).into()),
+            Some(FloatLit(2.0).into())
+        );
+    }
+
+    #[test]
+    fn evaluate_floats() {
+        assert_eq!(
+            OpKind::Less.evaluate(&FloatLit(1.5).into(), &FloatLit(2.0).into()),
+            Some(BoolLit(true).into())
+        );
+        assert_eq!(
+            OpKind::Mul.evaluate(&FloatLit(1.5).into(), &FloatLit(2.0).into()),
+            Some(FloatLit(3.0).into())
+        );
+        assert_eq!(
+            OpKind::Div.evaluate(&FloatLit(1.5).into(), &FloatLit(0.0).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::FloorDiv.evaluate(&FloatLit(7.5).into(), &FloatLit(2.0).into()),
+            Some(FloatLit(3.0).into())
+        );
+    }
+
+    #[test]
+    fn evaluate_bools() {
+        assert_eq!(
+            OpKind::And.evaluate(&BoolLit(true).into(), &BoolLit(false).into()),
+            Some(BoolLit(false).into())
+        );
+        assert_eq!(
+            OpKind::Or.evaluate(&BoolLit(true).into(), &BoolLit(false).into()),
+            Some(BoolLit(true).into())
+        );
+        assert_eq!(
+            OpKind::Plus.evaluate(&BoolLit(true).into(), &BoolLit(false).into()),
+            None
+        );
+    }
+
+    #[test]
+    fn evaluate_nones() {
+        assert_eq!(
+            OpKind::EqEq.evaluate(&ExprKind::None, &ExprKind::None),
+            Some(BoolLit(true).into())
+        );
+        assert_eq!(
+            OpKind::NotEq.evaluate(&ExprKind::None, &ExprKind::None),
+            Some(BoolLit(false).into())
+        );
+        assert_eq!(
+            OpKind::NotEq.evaluate(&ExprKind::None, &IntLit::new(5).into()),
+            Some(BoolLit(true).into())
+        );
+        assert_eq!(
+            OpKind::EqEq.evaluate(&ExprKind::None, &IntLit::new(5).into()),
+            Some(BoolLit(false).into())
+        );
+        assert_eq!(
+            OpKind::EqEq.evaluate(&StrLit("a".to_string()).into(), &ExprKind::None),
+            Some(BoolLit(false).into())
+        );
+        assert_eq!(
+            OpKind::Less.evaluate(&ExprKind::None, &IntLit::new(5).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::EqEq.evaluate(&ExprKind::None, &LetCall::from(Path::new("x")).into()),
+            None
+        );
+    }
+
+    #[test]
+    fn evaluate_mismatched() {
+        assert_eq!(
+            OpKind::Plus.evaluate(&IntLit::new(1).into(), &FloatLit(1.0).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::Plus.evaluate(&StrLit("a".to_string()).into(), &IntLit::new(1).into()),
+            None
+        );
+        assert_eq!(
+            OpKind::Plus.evaluate(
+                &LetCall::from(Path::new("x")).into(),
+                &IntLit::new(1).into()
+            ),
+            None
+        );
+    }
+}
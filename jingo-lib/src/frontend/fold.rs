@@ -0,0 +1,307 @@
+//! Constant folding, simplifying expressions made up of literals ahead of time
+//! whilst keeping the same meaning
+
+use super::ast::*;
+
+/// Folds all constant expressions found within `expr`, such as operations
+/// over two literals like `"a" + "b"` into `"ab"` using [OpKind::evaluate]
+pub fn fold_constants(expr: Expr) -> Expr {
+    let Expr {
+        kind,
+        doc,
+        start,
+        end,
+    } = expr;
+
+    let kind = match kind {
+        ExprKind::Op(op) => {
+            let left = fold_constants(*op.left);
+            let right = fold_constants(*op.right);
+
+            match op.kind.evaluate(&left.kind, &right.kind) {
+                Some(kind) => {
+                    return Expr {
+                        kind,
+                        doc,
+                        start: left.start,
+                        end: right.end,
+                    }
+                }
+                None => Op {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    kind: op.kind,
+                    grouped: op.grouped,
+                }
+                .into(),
+            }
+        }
+        ExprKind::Body(Body(exprs)) => fold_all(exprs).into(),
+        ExprKind::Interpolation(Interpolation(parts)) => Interpolation(fold_all(parts)).into(),
+        ExprKind::Not(Not(expr)) => {
+            let folded = fold_constants(*expr);
+
+            match folded.kind {
+                ExprKind::BoolLit(BoolLit(b)) => BoolLit(!b).into(),
+                ExprKind::Not(Not(inner)) => inner.kind, // double negation
+                _ => Not(Box::new(folded)).into(),
+            }
+        }
+        ExprKind::Neg(Neg(expr)) => {
+            return fold_negation(Expr {
+                kind: Neg(Box::new(fold_constants(*expr))).into(),
+                doc,
+                start,
+                end,
+            })
+        }
+        ExprKind::Class(class) => Class {
+            constructor: class.constructor.map(|constructor| Method {
+                body: fold_all(constructor.body),
+                ..constructor
+            }),
+            body: fold_all(class.body),
+            ..class
+        }
+        .into(),
+        ExprKind::Function(function) => Function {
+            body: fold_all(function.body),
+            ..function
+        }
+        .into(),
+        ExprKind::Method(method) => Method {
+            body: fold_all(method.body),
+            ..method
+        }
+        .into(),
+        ExprKind::FunctionCall(call) => FunctionCall {
+            args: fold_all(call.args),
+            ..call
+        }
+        .into(),
+        ExprKind::Match(matched) => Match {
+            kind: matched.kind,
+            condition: Box::new(fold_constants(*matched.condition)),
+            segments: matched
+                .segments
+                .into_iter()
+                .map(|seg| MatchSegment {
+                    pattern: match seg.pattern {
+                        Pattern::Expr(expr) => Pattern::Expr(Box::new(fold_constants(*expr))),
+                        Pattern::Wildcard => Pattern::Wildcard,
+                    },
+                    expr: Box::new(fold_constants(*seg.expr)),
+                })
+                .collect(),
+        }
+        .into(),
+        ExprKind::If(cond) => If {
+            segments: cond
+                .segments
+                .into_iter()
+                .map(|seg| IfSegment {
+                    condition: Box::new(fold_constants(*seg.condition)),
+                    body: fold_all(seg.body),
+                })
+                .collect(),
+            default: cond
+                .default
+                .map(|IfDefault(body)| IfDefault(fold_all(body))),
+        }
+        .into(),
+        ExprKind::While(looped) => While {
+            condition: Box::new(fold_constants(*looped.condition)),
+            body: fold_all(looped.body),
+        }
+        .into(),
+        ExprKind::Return(Return(expr)) => Return(Box::new(fold_constants(*expr))).into(),
+        ExprKind::Let(assigned) => Let {
+            expr: assigned.expr.map(|expr| Box::new(fold_constants(*expr))),
+            ..assigned
+        }
+        .into(),
+        ExprKind::LetSet(set) => LetSet {
+            expr: Box::new(fold_constants(*set.expr)),
+            ..set
+        }
+        .into(),
+        ExprKind::Cast(cast) => Cast {
+            expr: Box::new(fold_constants(*cast.expr)),
+            ..cast
+        }
+        .into(),
+        ExprKind::CondLet(cond) => CondLet {
+            expr: Box::new(fold_constants(*cond.expr)),
+            ..cond
+        }
+        .into(),
+        other => other,
+    };
+
+    Expr {
+        kind,
+        doc,
+        start,
+        end,
+    }
+}
+
+/// Collapses a negated literal such as `-5` into the literal `IntLit::new(-5)`,
+/// leaving anything else as-is. Negating `i64::MIN` would overflow, so it's
+/// left unfolded
+pub fn fold_negation(expr: Expr) -> Expr {
+    let negated = match &expr.kind {
+        ExprKind::Neg(Neg(inner)) => match &inner.kind {
+            ExprKind::IntLit(IntLit(int, radix)) => {
+                int.checked_neg().map(|int| IntLit(int, *radix).into())
+            }
+            ExprKind::FloatLit(FloatLit(float)) => Some(FloatLit(-float).into()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match negated {
+        Some(kind) => Expr { kind, ..expr },
+        None => expr,
+    }
+}
+
+/// Folds each expression in a body using [fold_constants]
+fn fold_all(exprs: Vec<Expr>) -> Vec<Expr> {
+    exprs.into_iter().map(fold_constants).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::parse;
+
+    /// Shortcut for parsing and folding a single expression
+    fn fparse(input: &str) -> Expr {
+        fold_constants(parse(input).unwrap().remove(0))
+    }
+
+    #[test]
+    fn string_concat() {
+        assert_eq!(
+            fparse(r#""a" + "b""#),
+            Expr {
+                kind: StrLit("ab".to_string()).into(),
+                doc: None,
+                start: 0,
+                end: 9
+            }
+        );
+        assert_eq!(
+            fparse(r#""a" + "b" + "c""#),
+            Expr {
+                kind: StrLit("abc".to_string()).into(),
+                doc: None,
+                start: 0,
+                end: 15
+            }
+        );
+        assert_eq!(
+            fparse(r#"while true { "hello " + "there" }"#),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: BoolLit(true).into(),
+                        doc: None,
+                        start: 6,
+                        end: 10
+                    }),
+                    body: vec![Expr {
+                        kind: StrLit("hello there".to_string()).into(),
+                        doc: None,
+                        start: 13,
+                        end: 31
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 33
+            }
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            fparse("1 + 2 + 3"),
+            Expr {
+                kind: IntLit::new(6).into(),
+                doc: None,
+                start: 0,
+                end: 9
+            }
+        );
+        assert_eq!(fparse("1 / 0"), parse("1 / 0").unwrap().remove(0));
+        assert_eq!(fparse("none == none").kind, BoolLit(true).into());
+        assert_eq!(fparse("5 / 2").kind, FloatLit(2.5).into());
+        assert_eq!(fparse("5 // 2").kind, IntLit::new(2).into());
+        assert_eq!(fparse("none != 5").kind, BoolLit(true).into());
+    }
+
+    #[test]
+    fn not_folding() {
+        assert_eq!(
+            fparse("!!true"),
+            Expr {
+                kind: BoolLit(true).into(),
+                doc: None,
+                start: 0,
+                end: 6
+            }
+        );
+        assert_eq!(fparse("!false").kind, BoolLit(true).into());
+        assert_eq!(fparse("!(1 == 2)").kind, BoolLit(true).into());
+        assert_eq!(fparse("!!x").kind, LetCall(Path::new("x")).into());
+        assert_eq!(fparse("!x"), parse("!x").unwrap().remove(0));
+    }
+
+    #[test]
+    fn negation() {
+        assert_eq!(
+            fparse("-5"),
+            Expr {
+                kind: IntLit::new(-5).into(),
+                doc: None,
+                start: 0,
+                end: 2
+            }
+        );
+        assert_eq!(fparse("-2.5").kind, FloatLit(-2.5).into());
+        assert_eq!(fparse("-5 + 3").kind, IntLit::new(-2).into());
+        assert_eq!(fparse("-(2 + 3)").kind, IntLit::new(-5).into());
+        assert_eq!(fparse("-2 ** 2").kind, IntLit::new(-4).into());
+        assert_eq!(fparse("(-2) ** 2").kind, IntLit::new(4).into());
+        assert_eq!(fparse("-x"), parse("-x").unwrap().remove(0));
+
+        let min = Expr {
+            kind: Neg(Box::new(Expr {
+                kind: IntLit::new(i64::MIN).into(),
+                doc: None,
+                start: 1,
+                end: 20,
+            }))
+            .into(),
+            doc: None,
+            start: 0,
+            end: 20,
+        };
+
+        assert_eq!(fold_negation(min.clone()), min);
+    }
+
+    #[test]
+    fn string_mixed() {
+        assert_eq!(fparse(r#""a" + 1"#), parse(r#""a" + 1"#).unwrap().remove(0));
+        assert_eq!(
+            fparse(r#""a" / "b""#),
+            parse(r#""a" / "b""#).unwrap().remove(0)
+        );
+    }
+}
@@ -2,10 +2,27 @@
 //! into further parsable tokens
 
 use super::ast::{Id, OpKind, Path};
-use logos::{Lexer, Logos};
+use crate::meta::MetaPos;
+use logos::{Lexer, Logos, Span};
+
+/// State shared by the lexer and parser whilst lexing a source, kept as the
+/// [logos] extras of each lexer
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LexState {
+    /// Byte offset of the most recent invalid escape found in a string or char
+    /// literal, set whilst lexing when it gives a [Token::Error]
+    pub bad_escape: Option<usize>,
+
+    /// End of the last token parsed, used to check that lexing progresses
+    pub last_end: usize,
+
+    /// If lexing stopped progressing, after which no more tokens are given
+    pub stalled: bool,
+}
 
 /// Lexed token from [logos], encompassing all possible tokens
-#[derive(Logos, Debug, PartialEq)]
+#[derive(Logos, Debug, Clone, PartialEq)]
+#[logos(extras = LexState)]
 pub enum Token {
     // single-char
     #[token("(")]
@@ -18,14 +35,14 @@ pub enum Token {
     BraceRight,
     #[token(",")]
     Comma,
+    #[token(":")]
+    Colon,
     #[token("!")]
     Exclaim,
     #[token("_")]
     Interpret,
-    #[token("*")]
-    Star, // TODO: figure out pointers
     #[token("-")]
-    Minus, // TODO: figure out negatives
+    Minus,
 
     // multi-char
     #[token("=")]
@@ -34,8 +51,11 @@ pub enum Token {
     FatArrow,
 
     // operation symbols
-    #[regex(r"\+|/|==|!=|<|<=|>|>=|and|or", get_op)]
+    #[regex(r"\+|\*|\*\*|/|//|==|!=|<|<=|>|>=|and|or|\+=|-=", get_op)]
     Op(OpKind),
+    #[token("===", |_| OpKind::EqEq)]
+    #[token("!==", |_| OpKind::NotEq)]
+    StrictOp(OpKind), // not in the language, used to give a helpful error
 
     // keywords
     #[token("match")]
@@ -48,6 +68,10 @@ pub enum Token {
     None,
     #[token("class")]
     Class,
+    #[token("if")]
+    If,
+    #[token("else")]
+    Else,
     #[token("while")]
     While,
     #[token("return")]
@@ -60,34 +84,532 @@ pub enum Token {
     Mut,
     #[token("fun")]
     Fun,
+    #[token("pub")]
+    Pub,
+    #[token("import")]
+    Import,
+    #[token("as")]
+    As,
 
     // literals
-    #[regex(r#""(\\"|[^"])*""#, get_str)]
+    #[regex(r#""([^"\\]|\\(.|\n))*""#, get_str)]
+    #[regex(r#""""([^"]|"[^"]|""[^"])*""""#, get_multiline_str)]
     Str(String),
-    #[regex(r"'([^'\n]|\\(\\|n|r|t|b|f|v|0|x[0-9a-fA-F]+))'", get_char)]
+    #[regex(
+        r"'([^'\\\n]|[\u{80}-\u{10FFFF}]|\\([^x\n]|x[0-9a-fA-F]+|u\{[0-9a-fA-F]*\}))'",
+        get_char
+    )]
+    // non-ascii range is explicit due to logos bug
     Char(u32),
-    #[regex(r"[0-9]*\.[0-9]+", get_float)]
+    #[regex(r"([0-9]+(_+[0-9]+)*)?\.[0-9]+(_+[0-9]+)*", get_float)]
     Float(f64),
-    #[regex(r"[0-9]+", get_int)]
+    #[regex(r"[0-9]+(_+[0-9]+)*", get_int)]
+    #[regex(r"0x[0-9a-fA-F]*", get_hex_int)]
+    #[regex(r"0b[01]*", get_bin_int)]
+    #[regex(r"0o[0-7]*", get_oct_int)]
     Int(i64),
-    #[regex(r"\.?[a-zA-Z_][a-zA-Z0-9_]*(\.[a-zA-Z_][a-zA-Z0-9_]*)*", get_path)]
+    #[regex(
+        r"\.?[\p{XID_Start}_][\p{XID_Continue}]*(\.[\p{XID_Start}_][\p{XID_Continue}]*)*",
+        get_path
+    )]
+    #[regex(r"r#[\p{XID_Start}_][\p{XID_Continue}]*", get_raw_path)]
     Path(Path),
 
     // misc
     #[regex(r"---.*(\n---.*)*", get_doc)] // would be ---.*(\n+---.*)* but logos bug
     Doc(String),
+    #[regex(r"--.*", get_comment)]
+    Comment(String),
 
     // special
     #[error]
-    #[regex(r"[ \t\n\f]+|(--.*)", logos::skip)]
+    #[regex(r"[ \t\n\f]+", logos::skip)]
+    Error,
+}
+
+impl Token {
+    /// Gets the [TokenKind] of this token without its data, used for cheaply
+    /// matching on the kind of a token
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::ParenLeft => TokenKind::ParenLeft,
+            Token::ParenRight => TokenKind::ParenRight,
+            Token::BraceLeft => TokenKind::BraceLeft,
+            Token::BraceRight => TokenKind::BraceRight,
+            Token::Comma => TokenKind::Comma,
+            Token::Colon => TokenKind::Colon,
+            Token::Exclaim => TokenKind::Exclaim,
+            Token::Interpret => TokenKind::Interpret,
+            Token::Minus => TokenKind::Minus,
+            Token::Equals => TokenKind::Equals,
+            Token::FatArrow => TokenKind::FatArrow,
+            Token::Op(_) => TokenKind::Op,
+            Token::StrictOp(_) => TokenKind::StrictOp,
+            Token::Match => TokenKind::Match,
+            Token::True => TokenKind::True,
+            Token::False => TokenKind::False,
+            Token::None => TokenKind::None,
+            Token::Class => TokenKind::Class,
+            Token::If => TokenKind::If,
+            Token::Else => TokenKind::Else,
+            Token::While => TokenKind::While,
+            Token::Return => TokenKind::Return,
+            Token::Break => TokenKind::Break,
+            Token::Let => TokenKind::Let,
+            Token::Mut => TokenKind::Mut,
+            Token::Fun => TokenKind::Fun,
+            Token::Pub => TokenKind::Pub,
+            Token::Import => TokenKind::Import,
+            Token::As => TokenKind::As,
+            Token::Str(_) => TokenKind::Str,
+            Token::Char(_) => TokenKind::Char,
+            Token::Float(_) => TokenKind::Float,
+            Token::Int(_) => TokenKind::Int,
+            Token::Path(_) => TokenKind::Path,
+            Token::Doc(_) => TokenKind::Doc,
+            Token::Comment(_) => TokenKind::Comment,
+            Token::Error => TokenKind::Error,
+        }
+    }
+
+    /// Gets a friendly name for the kind of this token, used in error messages
+    /// such as "unexpected keyword"
+    pub fn kind_name(&self) -> &'static str {
+        match self {
+            Token::ParenLeft
+            | Token::ParenRight
+            | Token::BraceLeft
+            | Token::BraceRight
+            | Token::Comma
+            | Token::Colon
+            | Token::Exclaim
+            | Token::Interpret
+            | Token::Minus
+            | Token::Equals
+            | Token::FatArrow => "symbol",
+            Token::Op(_) | Token::StrictOp(_) => "operator",
+            Token::Match
+            | Token::True
+            | Token::False
+            | Token::None
+            | Token::Class
+            | Token::If
+            | Token::Else
+            | Token::While
+            | Token::Return
+            | Token::Break
+            | Token::Let
+            | Token::Mut
+            | Token::Fun
+            | Token::Pub
+            | Token::Import
+            | Token::As => "keyword",
+            Token::Str(_) => "string",
+            Token::Char(_) => "character",
+            Token::Float(_) => "float",
+            Token::Int(_) => "integer",
+            Token::Path(_) => "path",
+            Token::Doc(_) => "documentation",
+            Token::Comment(_) => "comment",
+            Token::Error => "unknown token",
+        }
+    }
+
+    /// Gets the highlighting class of this token, used for [highlight]
+    pub fn category(&self) -> HlClass {
+        match self {
+            Token::True | Token::False | Token::None => HlClass::Literal,
+            Token::Str(_) => HlClass::String,
+            Token::Char(_) | Token::Float(_) | Token::Int(_) => HlClass::Literal,
+            Token::Path(_) => HlClass::Ident,
+            Token::Doc(_) | Token::Comment(_) => HlClass::Comment,
+            Token::Error => HlClass::Error,
+            Token::Match
+            | Token::Class
+            | Token::If
+            | Token::Else
+            | Token::While
+            | Token::Return
+            | Token::Break
+            | Token::Let
+            | Token::Mut
+            | Token::Fun
+            | Token::Pub
+            | Token::Import
+            | Token::As => HlClass::Keyword,
+            _ => HlClass::Operator,
+        }
+    }
+
+    /// Reconstructs canonical source text for this token which lexes back into
+    /// the same token, unlike the friendlier [Debug] output
+    pub fn lexeme(&self) -> String {
+        match self {
+            Token::ParenLeft => "(".to_string(),
+            Token::ParenRight => ")".to_string(),
+            Token::BraceLeft => "{".to_string(),
+            Token::BraceRight => "}".to_string(),
+            Token::Comma => ",".to_string(),
+            Token::Colon => ":".to_string(),
+            Token::Exclaim => "!".to_string(),
+            Token::Interpret => "_".to_string(),
+            Token::Minus => "-".to_string(),
+            Token::Equals => "=".to_string(),
+            Token::FatArrow => "=>".to_string(),
+            Token::Op(kind) => kind.to_string(),
+            Token::StrictOp(kind) => format!("{}=", kind),
+            Token::Match => "match".to_string(),
+            Token::True => "true".to_string(),
+            Token::False => "false".to_string(),
+            Token::None => "none".to_string(),
+            Token::Class => "class".to_string(),
+            Token::If => "if".to_string(),
+            Token::Else => "else".to_string(),
+            Token::While => "while".to_string(),
+            Token::Return => "return".to_string(),
+            Token::Break => "break".to_string(),
+            Token::Let => "let".to_string(),
+            Token::Mut => "mut".to_string(),
+            Token::Fun => "fun".to_string(),
+            Token::Pub => "pub".to_string(),
+            Token::Import => "import".to_string(),
+            Token::As => "as".to_string(),
+            Token::Str(string) => format!("\"{}\"", string),
+            Token::Char(c) => format!("'{}'", escape_char(*c)),
+            Token::Float(float) => {
+                let mut output = float.to_string();
+
+                if !output.contains('.') {
+                    output.push_str(".0");
+                }
+
+                output
+            }
+            Token::Int(int) => int.to_string(),
+            Token::Path(path) if path.fields.is_empty() && !path.affixed => {
+                let id = path.id.to_string();
+
+                match Token::lexer(&id).next() {
+                    Some(Token::Path(_)) => id,
+                    _ => format!("r#{}", id),
+                }
+            }
+            Token::Path(path) => path.to_string(),
+            Token::Doc(doc) => doc
+                .split('\n')
+                .map(|line| match line {
+                    "" => "---".to_string(),
+                    line => format!("--- {}", line),
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+            Token::Comment(comment) => format!("-- {}", comment),
+            Token::Error => String::new(),
+        }
+    }
+}
+
+/// Token along with both the line and column positions and the byte range it
+/// covers within its source, as used by editors
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocatedToken {
+    /// Token which was lexed
+    pub token: Token,
+
+    /// Position of the first character of the token
+    pub start: MetaPos,
+
+    /// Position just after the last character of the token
+    pub end: MetaPos,
+
+    /// Byte range of the token within its source
+    pub byte_range: Span,
+}
+
+/// Lexes `input` into tokens which each carry their [LocatedToken] positions,
+/// computed in a single pass alongside lexing
+pub fn scan_located(input: &str) -> Vec<LocatedToken> {
+    let mut lex = Token::lexer(input);
+    let mut pos = MetaPos::new();
+    let mut last = 0;
+    let mut output = vec![];
+
+    while let Some(token) = lex.next() {
+        let byte_range = lex.span();
+
+        pos.advance(&input[last..byte_range.start]);
+        let start = pos;
+        pos.advance(lex.slice());
+        last = byte_range.end;
+
+        output.push(LocatedToken {
+            token,
+            start,
+            end: pos,
+            byte_range,
+        });
+    }
+
+    output
+}
+
+/// Counts the tokens in `input` without collecting them, stopping at the first
+/// unknown or zero-length token and giving its [Span] instead
+pub fn count_tokens(input: &str) -> Result<usize, Span> {
+    let mut count = 0;
+
+    for item in guard_progress(Token::lexer(input).spanned()) {
+        let (token, span) = item?;
+
+        if token == Token::Error {
+            return Err(span);
+        }
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Guards spanned `tokens` against lexing which doesn't advance, giving the
+/// [Span] of the first token which is zero-length or starts before the end of
+/// the token before it as an error and then stopping. This protects lexing
+/// loops from hanging if a regex ever matches an empty string
+pub fn guard_progress<T>(
+    tokens: impl Iterator<Item = (T, Span)>,
+) -> impl Iterator<Item = Result<(T, Span), Span>> {
+    tokens.scan(Some(0), |last_end, (token, span)| {
+        if !progresses((*last_end)?, &span) {
+            *last_end = None;
+            Some(Err(span))
+        } else {
+            *last_end = Some(span.end);
+            Some(Ok((token, span)))
+        }
+    })
+}
+
+/// Checks if a token at `span` progresses past the `last_end` of the token
+/// before it, used by [guard_progress] and the parser to stop lexing which
+/// doesn't advance
+pub fn progresses(last_end: usize, span: &Span) -> bool {
+    !span.is_empty() && span.start >= last_end
+}
+
+/// Kind of a [Token] without any of the data it carries, as given by
+/// [Token::kind]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    ParenLeft,
+    ParenRight,
+    BraceLeft,
+    BraceRight,
+    Comma,
+    Colon,
+    Exclaim,
+    Interpret,
+    Minus,
+    Equals,
+    FatArrow,
+    Op,
+    StrictOp,
+    Match,
+    True,
+    False,
+    None,
+    Class,
+    If,
+    Else,
+    While,
+    Return,
+    Break,
+    Let,
+    Mut,
+    Fun,
+    Pub,
+    Import,
+    As,
+    Str,
+    Char,
+    Float,
+    Int,
+    Path,
+    Doc,
+    Comment,
     Error,
 }
 
+impl TokenKind {
+    /// Checks if this kind is a literal value, not including `none`
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Str
+                | TokenKind::Char
+                | TokenKind::Float
+                | TokenKind::Int
+                | TokenKind::True
+                | TokenKind::False
+        )
+    }
+}
+
+/// Highlighting class of a token, as given by [Token::category]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HlClass {
+    /// Keywords such as `fun` or `while`
+    Keyword,
+
+    /// Literal values which aren't strings, such as numbers or `true`
+    Literal,
+
+    /// Operators and other symbols such as braces
+    Operator,
+
+    /// Comments and documentation
+    Comment,
+
+    /// Identifiers as paths to nodes
+    Ident,
+
+    /// String literals
+    String,
+
+    /// Unknown tokens which couldn't be lexed
+    Error,
+}
+
+/// Checks if the [Token::Error] which `lex` just gave is a string which was
+/// never closed, giving the index of its opening quote if so. Unclosed strings
+/// carry on until the end of the input so they're found from their opening
+/// quote, whereas closed strings only error from an invalid escape inside
+pub fn unterminated_str(lex: &Lexer<Token>) -> Option<usize> {
+    let span = lex.span();
+    let bad_escape = lex.extras.bad_escape.is_some_and(|ind| span.contains(&ind));
+
+    (lex.slice().starts_with('"') && !bad_escape).then_some(span.start)
+}
+
+/// Highlights `input` into the [Span] of each token along with its class,
+/// used for rendering code. Comments are given as [HlClass::Comment] and unknown
+/// tokens are given as [HlClass::Error] instead of stopping
+pub fn highlight(input: &str) -> Vec<(Span, HlClass)> {
+    Token::lexer(input)
+        .spanned()
+        .map(|(token, span)| (span, token.category()))
+        .collect()
+}
+
+/// Token which a [Comment] is attached to, given by the [Span] of that token
+#[derive(Debug, Clone, PartialEq)]
+pub enum Attachment {
+    /// Attached to the token directly after the comment
+    Following(Span),
+
+    /// Attached to the token directly before the comment, for trailing comments
+    Preceding(Span),
+
+    /// No tokens to attach to, as the input is only comments
+    Unattached,
+}
+
+/// Ordinary `--` comment along with where it was found and what it's attached to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// Text of the comment without its leading `--`
+    pub text: String,
+
+    /// Byte range of the comment within its source
+    pub span: Span,
+
+    /// Token this comment is attached to
+    pub attachment: Attachment,
+}
+
+/// All ordinary comments of a source as collected by [collect_comments], which
+/// formatters may use to reattach comments to the nodes they describe
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommentMap(pub Vec<Comment>);
+
+impl CommentMap {
+    /// Gets the comments attached to the token starting at `start`, such as
+    /// the [Expr](super::ast::Expr::start) of a node
+    pub fn attached_to(&self, start: usize) -> Vec<&Comment> {
+        self.0
+            .iter()
+            .filter(|comment| match &comment.attachment {
+                Attachment::Following(span) | Attachment::Preceding(span) => span.start == start,
+                Attachment::Unattached => false,
+            })
+            .collect()
+    }
+}
+
+/// Collects each ordinary comment in `input`, as lexed into [Token::Comment]
+/// tokens, into a [CommentMap]. Comments on the same line as the token before
+/// them are trailing comments and attach to that token, otherwise they attach
+/// to the token after them. If there is no token after, they attach to the
+/// token before
+pub fn collect_comments(input: &str) -> CommentMap {
+    let tokens: Vec<(Token, Span)> = Token::lexer(input).spanned().collect();
+    let mut comments = vec![];
+
+    for (ind, (token, span)) in tokens.iter().enumerate() {
+        let text = match token {
+            Token::Comment(text) => text.clone(),
+            _ => continue,
+        };
+        let is_code = |(token, _): &&(Token, Span)| !matches!(token, Token::Comment(_));
+        let preceding = tokens[..ind].iter().rev().find(is_code);
+        let following = tokens[ind + 1..].iter().find(is_code);
+
+        let attachment = match (preceding, following) {
+            (Some((_, before)), _) if !input[before.end..span.start].contains('\n') => {
+                Attachment::Preceding(before.clone())
+            }
+            (_, Some((_, after))) => Attachment::Following(after.clone()),
+            (Some((_, before)), None) => Attachment::Preceding(before.clone()),
+            (None, None) => Attachment::Unattached,
+        };
+
+        comments.push(Comment {
+            text,
+            span: span.clone(),
+            attachment,
+        });
+    }
+
+    CommentMap(comments)
+}
+
+/// Escapes the inside of a char literal where the lexer requires it, used for
+/// reconstructing [Token::Char] source
+pub(crate) fn escape_char(c: u32) -> String {
+    match c {
+        0x0A => "\\n".to_string(),
+        0x0D => "\\r".to_string(),
+        0x09 => "\\t".to_string(),
+        0x07 => "\\a".to_string(),
+        0x08 => "\\b".to_string(),
+        0x0B => "\\v".to_string(),
+        0x0C => "\\f".to_string(),
+        0x00 => "\\0".to_string(),
+        0x5C => "\\\\".to_string(),
+        c => match char::from_u32(c) {
+            Some(found) if !found.is_control() && found != '\'' => found.to_string(),
+            _ => format!("\\x{:X}", c),
+        },
+    }
+}
+
 fn get_op(lex: &mut Lexer<Token>) -> OpKind {
     match lex.slice() {
         "+" => OpKind::Plus,
         "-" => OpKind::Sub,
+        "*" => OpKind::Mul,
         "/" => OpKind::Div,
+        "//" => OpKind::FloorDiv,
+        "**" => OpKind::Pow,
         "==" => OpKind::EqEq,
         "!=" => OpKind::NotEq,
         "<" => OpKind::Less,
@@ -96,6 +618,8 @@ fn get_op(lex: &mut Lexer<Token>) -> OpKind {
         ">=" => OpKind::GreaterEq,
         "and" => OpKind::And,
         "or" => OpKind::Or,
+        "+=" => OpKind::PlusEq,
+        "-=" => OpKind::SubEq,
         _ => panic!(), // regex prevents
     }
 }
@@ -103,39 +627,134 @@ fn get_op(lex: &mut Lexer<Token>) -> OpKind {
 fn get_str(lex: &mut Lexer<Token>) -> Option<String> {
     let slice = lex.slice();
     let found = &slice[1..slice.len() - 1];
+    let mut chars = found.char_indices().peekable();
+
+    while let Some((ind, c)) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+
+        let valid = match chars.next() {
+            Some((_, 'x')) => {
+                let mut digits = 0;
+
+                while chars.next_if(|(_, c)| c.is_ascii_hexdigit()).is_some() {
+                    digits += 1;
+                }
+
+                digits != 0 && digits <= 8
+            }
+            Some((_, 'u')) => {
+                let mut hex = String::new();
+                let opened = chars.next_if(|(_, c)| *c == '{').is_some();
+
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+                    hex.push(c);
+                }
+
+                opened
+                    && chars.next_if(|(_, c)| *c == '}').is_some()
+                    && unicode_to_u32(&hex).is_some()
+            }
+            Some((_, '\\' | '"')) => true,
+            Some((_, c)) => escape_value(c).is_some(),
+            None => false,
+        };
+
+        if !valid {
+            lex.extras.bad_escape = Some(lex.span().start + 1 + ind);
+            return None;
+        }
+    }
+
+    Some(found.to_string())
+}
+
+/// Gets a triple-quoted multi-line string such as `"""\nhello\n"""`, trimming
+/// an immediate leading newline. These are raw so quotes and backslashes inside
+/// are kept as-is, being escaped in the resulting [Token::Str]
+fn get_multiline_str(lex: &mut Lexer<Token>) -> String {
+    let slice = lex.slice();
+    let found = &slice[3..slice.len() - 3];
+    let found = found.strip_prefix('\n').unwrap_or(found);
+
+    escape_raw(found)
+}
 
-    (found != "\\").then_some(found.to_string())
+/// Escapes the backslashes and quotes of raw text from a triple-quoted string,
+/// giving the escaped form a [Token::Str] holds
+pub fn escape_raw(raw: &str) -> String {
+    raw.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
 fn get_char(lex: &mut Lexer<Token>) -> Option<u32> {
     let mut chars = lex.slice().chars();
     chars.next();
 
-    match chars.next().unwrap() {
+    let found = match chars.next().unwrap() {
         '\\' => match chars.next().unwrap() {
-            'n' => Some('\n' as u32),   // newline
-            'r' => Some('\r' as u32),   // carriage return
-            't' => Some('\t' as u32),   // tab
-            'b' => Some('\x7f' as u32), // backspace
-            'f' => Some('\x0C' as u32), // form feed
-            '0' => Some('\0' as u32),
             'x' => {
                 chars.next_back();
                 hex_to_u32(chars.as_str(), 8)
             } // hex
-            _ => panic!(), // regex prevents
+            'u' => {
+                chars.next_back();
+                chars
+                    .as_str()
+                    .strip_prefix('{')
+                    .and_then(|braced| braced.strip_suffix('}'))
+                    .and_then(unicode_to_u32)
+            } // unicode
+            '\\' => Some('\\' as u32),
+            c => escape_value(c),
         },
-        c => Some(c as u32), // normal
+        c => return Some(c as u32), // normal
+    };
+
+    if found.is_none() {
+        lex.extras.bad_escape = Some(lex.span().start + 1);
+    }
+
+    found
+}
+
+/// Gets the value of a single-character escape such as the `n` in `\n`, giving
+/// [None] if it isn't a known escape
+fn escape_value(c: char) -> Option<u32> {
+    match c {
+        'n' => Some('\n' as u32),   // newline
+        'r' => Some('\r' as u32),   // carriage return
+        't' => Some('\t' as u32),   // tab
+        'a' => Some('\x07' as u32), // bell
+        'b' => Some('\x08' as u32), // backspace
+        'v' => Some('\x0B' as u32), // vertical tab
+        'f' => Some('\x0C' as u32), // form feed
+        '0' => Some('\0' as u32),
+        _ => None,
     }
 }
 
+/// Converts the hex digits of a `\u{...}` escape into a [u32] value, giving
+/// [None] if it isn't a valid unicode scalar value such as a surrogate
+fn unicode_to_u32(hex: &str) -> Option<u32> {
+    if hex.is_empty() || hex.len() > 6 {
+        return None;
+    }
+
+    char::from_u32(u32::from_str_radix(hex, 16).ok()?).map(|c| c as u32)
+}
+
 /// Converts character iterator of hex digits into a [u32] value
 fn hex_to_u32(hex: &str, limit: usize) -> Option<u32> {
-    (hex.len() <= limit).then_some(u32::from_str_radix(hex, 16).unwrap())
+    if hex.len() > limit {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok()
 }
 
 fn get_float(lex: &mut Lexer<Token>) -> Option<f64> {
-    lex.slice().parse().ok()
+    strip_separators(lex.slice())?.parse().ok()
 }
 
 fn get_path(lex: &mut Lexer<Token>) -> Path {
@@ -157,22 +776,64 @@ fn get_path(lex: &mut Lexer<Token>) -> Path {
     }
 }
 
+/// Gets a raw identifier such as `r#class`, letting keywords be used as a [Path]
+fn get_raw_path(lex: &mut Lexer<Token>) -> Path {
+    Path::new(&lex.slice()[2..])
+}
+
 fn get_int(lex: &mut Lexer<Token>) -> Option<i64> {
-    lex.slice().parse().ok()
+    strip_separators(lex.slice())?.parse().ok()
+}
+
+/// Strips the underscore digit separators from a numeric literal such as
+/// `1_000`, giving nothing if one leads or trails the digits of either side of
+/// the decimal point
+fn strip_separators(slice: &str) -> Option<String> {
+    if slice
+        .split('.')
+        .any(|digits| digits.starts_with('_') || digits.ends_with('_'))
+    {
+        None
+    } else {
+        Some(slice.replace('_', ""))
+    }
+}
+
+/// Gets a hexadecimal integer such as `0xFF`, giving an error if it overflows
+/// or has no digits
+fn get_hex_int(lex: &mut Lexer<Token>) -> Option<i64> {
+    i64::from_str_radix(&lex.slice()[2..], 16).ok()
+}
+
+/// Gets a binary integer such as `0b1010`, giving an error if it overflows or
+/// has no digits
+fn get_bin_int(lex: &mut Lexer<Token>) -> Option<i64> {
+    i64::from_str_radix(&lex.slice()[2..], 2).ok()
+}
+
+/// Gets an octal integer such as `0o777`, giving an error if it overflows or
+/// has no digits
+fn get_oct_int(lex: &mut Lexer<Token>) -> Option<i64> {
+    i64::from_str_radix(&lex.slice()[2..], 8).ok()
 }
 
 fn get_doc(lex: &mut Lexer<Token>) -> String {
     lex.slice()
         .split('\n')
-        .filter(|l| l.len() != 0)
+        .filter(|l| !l.is_empty())
         .map(|l| l[3..].trim())
         .collect::<Vec<&str>>()
         .join("\n")
 }
 
+fn get_comment(lex: &mut Lexer<Token>) -> String {
+    lex.slice()[2..].trim().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn chars() {
@@ -181,6 +842,11 @@ mod tests {
         assert_eq!(lex.next().unwrap(), Token::Char('a' as u32));
         assert_eq!(lex.next().unwrap(), Token::Char('b' as u32));
         assert_eq!(lex.next().unwrap(), Token::Char('\n' as u32));
+
+        let mut lex = Token::lexer("'é' '😀'");
+
+        assert_eq!(lex.next().unwrap(), Token::Char('é' as u32));
+        assert_eq!(lex.next().unwrap(), Token::Char('😀' as u32));
     }
 
     #[test]
@@ -205,10 +871,60 @@ mod tests {
                 affixed: false
             })
         );
+        assert_eq!(lex.next().unwrap(), Token::Comment("comment!".to_string()));
         assert_eq!(lex.next().unwrap(), Token::Doc("docstring".to_string()));
         assert_eq!(lex.next().unwrap(), Token::True);
     }
 
+    #[test]
+    fn hex_ints() {
+        let mut lex = Token::lexer("0xFF 0x0 0xdeadBEEF 0x8000000000000000 0x7FFFFFFFFFFFFFFF");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(255));
+        assert_eq!(lex.next().unwrap(), Token::Int(0));
+        assert_eq!(lex.next().unwrap(), Token::Int(0xdeadbeef));
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.next().unwrap(), Token::Int(i64::MAX));
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn bin_oct_ints() {
+        let mut lex = Token::lexer("0b1010 0o17 0o777 0b0");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(10));
+        assert_eq!(lex.next().unwrap(), Token::Int(15));
+        assert_eq!(lex.next().unwrap(), Token::Int(511));
+        assert_eq!(lex.next().unwrap(), Token::Int(0));
+        assert_eq!(lex.next(), None);
+
+        let too_large = format!("0b1{}", "0".repeat(63));
+
+        assert_eq!(Token::lexer(&too_large).next().unwrap(), Token::Error);
+        assert_eq!(Token::lexer("0b").next().unwrap(), Token::Error);
+        assert_eq!(Token::lexer("0o").next().unwrap(), Token::Error);
+        assert_eq!(Token::lexer("0x").next().unwrap(), Token::Error);
+    }
+
+    #[test]
+    fn digit_separators() {
+        let mut lex = Token::lexer("1_000 3_141.592 0.000_1 1__0 1_000_000");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(1000));
+        assert_eq!(lex.next().unwrap(), Token::Float(3141.592));
+        assert_eq!(lex.next().unwrap(), Token::Float(0.0001));
+        assert_eq!(lex.next().unwrap(), Token::Int(10));
+        assert_eq!(lex.next().unwrap(), Token::Int(1_000_000));
+        assert_eq!(lex.next(), None);
+
+        let mut lex = Token::lexer("_5 5_ 1_.5 1._5");
+
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("_5")));
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_ne!(lex.next().unwrap(), Token::Float(1.5));
+    }
+
     #[test]
     fn check_get_doc() {
         let mut lex = Token::lexer("--- hello\n---there\n---\n---  woo \n--- singleliner ---\n");
@@ -238,6 +954,219 @@ mod tests {
         );
     }
 
+    #[test]
+    fn comments() {
+        assert_eq!(
+            Token::lexer("-- hi").next().unwrap(),
+            Token::Comment("hi".to_string())
+        );
+
+        let mut lex = Token::lexer("x -- hi\n--- doc");
+
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("x")));
+        assert_eq!(lex.next().unwrap(), Token::Comment("hi".to_string()));
+        assert_eq!(lex.next().unwrap(), Token::Doc("doc".to_string()));
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn unterminated_strings() {
+        let mut lex = Token::lexer("x = \"unterminated\nmore code");
+
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("x")));
+        assert_eq!(lex.next().unwrap(), Token::Equals);
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(unterminated_str(&lex), Some(4));
+
+        let mut lex = Token::lexer("\"\"\"never closed\"\" x");
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(unterminated_str(&lex), Some(0));
+
+        let mut lex = Token::lexer("\"a\\qb\" #");
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(unterminated_str(&lex), None);
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(unterminated_str(&lex), None);
+    }
+
+    #[test]
+    fn multiline_strings() {
+        let mut lex = Token::lexer("\"\"\"\nhello\n\"\"\" \"\"\"say \"hi\" \\n\"\"\" \"\" \"a\"");
+
+        assert_eq!(lex.next().unwrap(), Token::Str("hello\n".to_string()));
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::Str("say \\\"hi\\\" \\\\n".to_string())
+        );
+        assert_eq!(lex.next().unwrap(), Token::Str(String::new()));
+        assert_eq!(lex.next().unwrap(), Token::Str("a".to_string()));
+        assert_eq!(lex.next(), None);
+    }
+
+    #[test]
+    fn lexemes() {
+        let tokens = vec![
+            Token::Str("hello \\\"there\\\"".to_string()),
+            Token::Str(String::new()),
+            Token::Int(5),
+            Token::Float(0.01),
+            Token::Float(12.0),
+            Token::Char('a' as u32),
+            Token::Char('\n' as u32),
+            Token::Char('\'' as u32),
+            Token::Op(OpKind::Plus),
+            Token::Op(OpKind::GreaterEq),
+            Token::Op(OpKind::And),
+            Token::StrictOp(OpKind::NotEq),
+            Token::Path(Path {
+                id: "c".into(),
+                fields: vec!["a".into(), "b".into()],
+                affixed: true,
+            }),
+            Token::Path(Path::new("class")),
+            Token::Doc("hi\n\nthere".to_string()),
+            Token::FatArrow,
+            Token::Match,
+            Token::BraceLeft,
+        ];
+
+        for token in tokens {
+            let lexeme = token.lexeme();
+            let mut lex = Token::lexer(&lexeme);
+
+            assert_eq!(lex.next(), Some(token));
+            assert_eq!(lex.next(), None);
+        }
+
+        assert_eq!(Token::Op(OpKind::Plus).lexeme(), "+");
+        assert_eq!(Token::Int(5).lexeme(), "5");
+        assert_eq!(Token::Str("s".to_string()).lexeme(), "\"s\"");
+    }
+
+    #[test]
+    fn located() {
+        assert_eq!(
+            scan_located("let x\n  = 'é'"),
+            vec![
+                LocatedToken {
+                    token: Token::Let,
+                    start: MetaPos { line: 1, col: 1 },
+                    end: MetaPos { line: 1, col: 4 },
+                    byte_range: 0..3
+                },
+                LocatedToken {
+                    token: Token::Path(Path::new("x")),
+                    start: MetaPos { line: 1, col: 5 },
+                    end: MetaPos { line: 1, col: 6 },
+                    byte_range: 4..5
+                },
+                LocatedToken {
+                    token: Token::Equals,
+                    start: MetaPos { line: 2, col: 3 },
+                    end: MetaPos { line: 2, col: 4 },
+                    byte_range: 8..9
+                },
+                LocatedToken {
+                    token: Token::Char('é' as u32),
+                    start: MetaPos { line: 2, col: 5 },
+                    end: MetaPos { line: 2, col: 8 },
+                    byte_range: 10..14
+                }
+            ]
+        );
+        assert_eq!(
+            scan_located("--- a\n--- b\nx")[1].start,
+            MetaPos { line: 3, col: 1 }
+        );
+    }
+
+    #[test]
+    fn counting() {
+        let input = "fun hello(a b) { let x = 'c' -- comment\n\"hi\" + 2.5 }";
+
+        assert_eq!(
+            count_tokens(input),
+            Ok(Token::lexer(input).collect::<Vec<Token>>().len())
+        );
+        assert_eq!(count_tokens(""), Ok(0));
+        assert_eq!(count_tokens("let x = #"), Err(8..9));
+    }
+
+    #[test]
+    fn progress_guarding() {
+        let stalling = vec![('a', 0..1), ('b', 1..1), ('c', 1..1), ('d', 1..2)];
+
+        assert_eq!(
+            guard_progress(stalling.into_iter()).collect::<Vec<_>>(),
+            vec![Ok(('a', 0..1)), Err(1..1)]
+        );
+        assert_eq!(
+            guard_progress(vec![('a', 0..2), ('b', 1..3)].into_iter()).collect::<Vec<_>>(),
+            vec![Ok(('a', 0..2)), Err(1..3)]
+        );
+        assert_eq!(
+            guard_progress(std::iter::repeat(('a', 0..0)))
+                .take(5)
+                .count(),
+            1
+        );
+        assert!(
+            guard_progress(Token::lexer("let x = 5 -- hi\n'a'").spanned()).all(|item| item.is_ok())
+        );
+    }
+
+    #[test]
+    fn token_kinds() {
+        assert_ne!(Token::Int(5).kind(), Token::Float(1.0).kind());
+        assert!(Token::Int(5).kind().is_literal());
+        assert!(Token::Float(1.0).kind().is_literal());
+        assert!(Token::True.kind().is_literal());
+        assert!(!Token::None.kind().is_literal());
+        assert!(!Token::Path(Path::new("x")).kind().is_literal());
+        assert_eq!(
+            Token::Op(OpKind::Plus).kind(),
+            Token::Op(OpKind::Sub).kind()
+        );
+        assert_eq!(Token::Op(OpKind::Plus).kind(), TokenKind::Op);
+    }
+
+    #[test]
+    fn mul_ops() {
+        let mut lex = Token::lexer("2 * 3");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(2));
+        assert_eq!(lex.next().unwrap(), Token::Op(OpKind::Mul));
+        assert_eq!(lex.next().unwrap(), Token::Int(3));
+        assert_eq!(Token::Op(OpKind::Mul).lexeme(), "*");
+    }
+
+    #[test]
+    fn compound_ops() {
+        let mut lex = Token::lexer("x += 1 y -= 2 - 3");
+
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::Op(OpKind::PlusEq));
+        lex.next();
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::Op(OpKind::SubEq));
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::Minus);
+    }
+
+    #[test]
+    fn strict_ops() {
+        let mut lex = Token::lexer("a === b !== c == d");
+
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::StrictOp(OpKind::EqEq));
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::StrictOp(OpKind::NotEq));
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::Op(OpKind::EqEq));
+    }
+
     #[test]
     fn strings() {
         assert_eq!(
@@ -247,6 +1176,82 @@ mod tests {
         assert_eq!(Token::lexer("\"\\\"").next().unwrap(), Token::Error);
     }
 
+    #[test]
+    fn escape_errors() {
+        let mut lex = Token::lexer("\"a\\qb\"");
+
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.extras.bad_escape, Some(2));
+
+        let mut lex = Token::lexer("x '\\q'");
+
+        lex.next();
+        assert_eq!(lex.next().unwrap(), Token::Error);
+        assert_eq!(lex.extras.bad_escape, Some(3));
+
+        let mut lex = Token::lexer("\"\\n\\\"\\xFF\\\\\" '\\\\'");
+
+        assert_eq!(
+            lex.next().unwrap(),
+            Token::Str("\\n\\\"\\xFF\\\\".to_string())
+        );
+        assert_eq!(lex.next().unwrap(), Token::Char('\\' as u32));
+        assert_eq!(lex.extras.bad_escape, None);
+    }
+
+    #[test]
+    fn char_escapes() {
+        let mut lex = Token::lexer(r"'\b' '\f' '\v' '\t' '\a'");
+
+        assert_eq!(lex.next().unwrap(), Token::Char(8));
+        assert_eq!(lex.next().unwrap(), Token::Char(0x0C));
+        assert_eq!(lex.next().unwrap(), Token::Char(0x0B));
+        assert_eq!(lex.next().unwrap(), Token::Char(9));
+        assert_eq!(lex.next().unwrap(), Token::Char(7));
+        assert_eq!(lex.next(), None);
+        assert_eq!(Token::Char(8).lexeme(), r"'\b'");
+        assert_eq!(Token::Char(0x0B).lexeme(), r"'\v'");
+        assert_eq!(Token::Char(7).lexeme(), r"'\a'");
+        assert_eq!(
+            Token::lexer(r#""\a\v""#).next().unwrap(),
+            Token::Str(r"\a\v".to_string())
+        );
+
+        for c in (0..=0x7F).filter_map(char::from_u32) {
+            let escape = format!("'\\{}'", c);
+            let _ = Token::lexer(&escape).next(); // every admitted escape decodes without panicking
+        }
+        assert_eq!(Token::Char(0x7F).lexeme(), r"'\x7F'");
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        let mut lex = Token::lexer(r"'\u{41}' '\u{1F600}' '\u{10FFFF}'");
+
+        assert_eq!(lex.next().unwrap(), Token::Char(65));
+        assert_eq!(lex.next().unwrap(), Token::Char(0x1F600));
+        assert_eq!(lex.next().unwrap(), Token::Char(0x10FFFF));
+        assert_eq!(lex.next(), None);
+
+        for invalid in [r"'\u{110000}'", r"'\u{D800}'", r"'\u{}'", r"'\u'"] {
+            assert_eq!(Token::lexer(invalid).next().unwrap(), Token::Error);
+        }
+
+        assert_eq!(
+            Token::lexer(r#""a \u{41} b""#).next().unwrap(),
+            Token::Str(r"a \u{41} b".to_string())
+        );
+
+        for invalid in [
+            r#""\u{110000}""#,
+            r#""\u{DFFF}""#,
+            r#""\u41""#,
+            r#""\u{41""#,
+        ] {
+            assert_eq!(Token::lexer(invalid).next().unwrap(), Token::Error);
+        }
+    }
+
     #[test]
     fn char_hex() {
         assert_eq!(hex_to_u32("F", 1).unwrap(), 15);
@@ -260,6 +1265,9 @@ mod tests {
         assert_eq!(hex_to_u32("0000", 3), None);
         assert_eq!(hex_to_u32("FFFFF", 3), None);
         assert_eq!(hex_to_u32("00000", 3), None);
+        assert_eq!(hex_to_u32("123456789", 8), None);
+        assert_eq!(Token::lexer(r"'\x123456789'").next().unwrap(), Token::Error);
+        assert_eq!(Token::lexer(r"'\xFFFFFFFFF'").next().unwrap(), Token::Error);
 
         let mut lex = Token::lexer(r#"'\xF' '\xA' '\0' '\xFF' '\xA0CF' '\xfe10'"#);
 
@@ -271,6 +1279,100 @@ mod tests {
         assert_eq!(lex.next().unwrap(), Token::Char(65040));
     }
 
+    #[test]
+    fn unicode_pathing() {
+        assert_eq!(
+            Token::lexer("café").next().unwrap(),
+            Token::Path(Path::new("café"))
+        );
+        assert_eq!(
+            Token::lexer("λόγος.ἀρχή").next().unwrap(),
+            Token::Path(Path {
+                id: "ἀρχή".into(),
+                fields: vec!["λόγος".into()],
+                affixed: false
+            })
+        );
+        assert_eq!(
+            Token::lexer("_über9").next().unwrap(),
+            Token::Path(Path::new("_über9"))
+        );
+
+        let mut lex = Token::lexer("1abc");
+
+        assert_eq!(lex.next().unwrap(), Token::Int(1));
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("abc")));
+        assert_eq!(Token::lexer("_").next().unwrap(), Token::Interpret);
+        assert_eq!(Token::lexer("match").next().unwrap(), Token::Match);
+        assert_eq!(Token::lexer("and").next().unwrap(), Token::Op(OpKind::And));
+    }
+
+    #[test]
+    fn highlighting() {
+        assert_eq!(
+            highlight("fun f() { \"hi\" }"),
+            vec![
+                (0..3, HlClass::Keyword),
+                (4..5, HlClass::Ident),
+                (5..6, HlClass::Operator),
+                (6..7, HlClass::Operator),
+                (8..9, HlClass::Operator),
+                (10..14, HlClass::String),
+                (15..16, HlClass::Operator),
+            ]
+        );
+        assert_eq!(
+            highlight("true $ -- hi"),
+            vec![
+                (0..4, HlClass::Literal),
+                (5..6, HlClass::Error),
+                (7..12, HlClass::Comment),
+            ]
+        );
+    }
+
+    #[test]
+    fn comment_maps() {
+        let map = collect_comments("let x = 1\n-- about y\nlet y = 2 -- trailing\n-- end");
+
+        assert_eq!(
+            map.0,
+            vec![
+                Comment {
+                    text: "about y".to_string(),
+                    span: 10..20,
+                    attachment: Attachment::Following(21..24)
+                },
+                Comment {
+                    text: "trailing".to_string(),
+                    span: 31..42,
+                    attachment: Attachment::Preceding(29..30)
+                },
+                Comment {
+                    text: "end".to_string(),
+                    span: 43..49,
+                    attachment: Attachment::Preceding(29..30)
+                },
+            ]
+        );
+        assert_eq!(map.attached_to(21), vec![&map.0[0]]);
+        assert_eq!(
+            collect_comments("-- alone").0[0].attachment,
+            Attachment::Unattached
+        );
+    }
+
+    #[test]
+    fn raw_identifiers() {
+        let mut lex = Token::lexer("r#class class r#x r");
+
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("class")));
+        assert_eq!(lex.next().unwrap(), Token::Class);
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("x")));
+        assert_eq!(lex.next().unwrap(), Token::Path(Path::new("r")));
+        assert_eq!(lex.next(), None);
+    }
+
     #[test]
     fn pathing() {
         // eq
@@ -337,4 +1439,97 @@ mod tests {
             })
         );
     }
+
+    /// Generates tokens which can appear next to each other when separated by
+    /// spaces, so excluding line-ending tokens such as [Token::Doc]
+    fn any_token() -> impl Strategy<Value = Token> {
+        const KEYWORDS: &[&str] = &[
+            "match", "true", "false", "none", "class", "if", "else", "while", "return", "break",
+            "let", "mut", "fun", "pub", "import", "as", "and", "or", "_",
+        ];
+
+        prop_oneof![
+            prop::sample::select(vec![
+                Token::ParenLeft,
+                Token::ParenRight,
+                Token::BraceLeft,
+                Token::BraceRight,
+                Token::Comma,
+                Token::Colon,
+                Token::Exclaim,
+                Token::Interpret,
+                Token::Minus,
+                Token::Equals,
+                Token::FatArrow,
+                Token::Match,
+                Token::True,
+                Token::False,
+                Token::None,
+                Token::Class,
+                Token::If,
+                Token::Else,
+                Token::While,
+                Token::Return,
+                Token::Break,
+                Token::Let,
+                Token::Mut,
+                Token::Fun,
+                Token::Pub,
+                Token::Import,
+                Token::As,
+            ]),
+            prop::sample::select(vec![
+                OpKind::Plus,
+                OpKind::Mul,
+                OpKind::Div,
+                OpKind::FloorDiv,
+                OpKind::Pow,
+                OpKind::EqEq,
+                OpKind::NotEq,
+                OpKind::Less,
+                OpKind::LessEq,
+                OpKind::Greater,
+                OpKind::GreaterEq,
+                OpKind::And,
+                OpKind::Or,
+                OpKind::PlusEq,
+                OpKind::SubEq,
+            ])
+            .prop_map(Token::Op),
+            prop::sample::select(vec![OpKind::EqEq, OpKind::NotEq]).prop_map(Token::StrictOp),
+            r#"([a-zA-Z0-9 _]|\\[nrtbf0"\\])*"#.prop_map(Token::Str),
+            any::<char>().prop_map(|c| Token::Char(c as u32)),
+            (0.0f64..1e12).prop_map(Token::Float),
+            (0..=i64::MAX).prop_map(Token::Int),
+            (
+                any::<bool>(),
+                prop::collection::vec("[a-zA-Z_][a-zA-Z0-9_]{0,6}", 1..4)
+            )
+                .prop_filter("paths can't be keywords", |(affixed, ids)| {
+                    *affixed || ids.len() > 1 || !KEYWORDS.contains(&ids[0].as_str())
+                })
+                .prop_map(|(affixed, mut ids)| {
+                    let id = ids.pop().unwrap().into();
+
+                    Token::Path(Path {
+                        fields: ids.into_iter().map(|id| id.into()).collect(),
+                        id,
+                        affixed,
+                    })
+                }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn lexeme_round_trip(tokens in prop::collection::vec(any_token(), 0..16)) {
+            let source = tokens
+                .iter()
+                .map(|token| token.lexeme())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            prop_assert_eq!(Token::lexer(&source).collect::<Vec<Token>>(), tokens);
+        }
+    }
 }
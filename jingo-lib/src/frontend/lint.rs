@@ -0,0 +1,268 @@
+//! Style lints over the abstract syntax tree, finding code which is valid but
+//! is likely to be a mistake
+
+use super::ast::*;
+
+/// Finds the starting index of every `fun`, `class` or `while` with an empty
+/// body, as these are often left unintentionally
+pub fn find_empty_bodies(exprs: &[Expr]) -> Vec<usize> {
+    let mut found = vec![];
+
+    for expr in exprs {
+        empty_bodies_walk(expr, &mut found);
+    }
+
+    found
+}
+
+/// Recursive walker for [find_empty_bodies]
+fn empty_bodies_walk(expr: &Expr, found: &mut Vec<usize>) {
+    let empty = match &expr.kind {
+        ExprKind::Function(function) => function.body.is_empty(),
+        ExprKind::Method(method) => method.body.is_empty(),
+        ExprKind::Class(class) => class.body.is_empty() && class.constructor.is_none(),
+        ExprKind::While(looped) => looped.body.is_empty(),
+        _ => false,
+    };
+
+    if empty {
+        found.push(expr.start);
+    }
+
+    for child in expr.children() {
+        empty_bodies_walk(child, found);
+    }
+}
+
+/// Finds the starting index of every `let` which redeclares a path already
+/// declared by an earlier `let` in the same body, as this is likely a mistake.
+/// Shadowing a `let` from an outer body inside a nested one is allowed
+pub fn find_shadowed_lets(exprs: &[Expr]) -> Vec<usize> {
+    let mut found = vec![];
+    shadowed_lets_scope(exprs, &mut found);
+    found
+}
+
+/// Checks a single body for [find_shadowed_lets], then any bodies nested inside
+fn shadowed_lets_scope(exprs: &[Expr], found: &mut Vec<usize>) {
+    let mut seen: Vec<&Path> = vec![];
+
+    for expr in exprs {
+        if let ExprKind::Let(assigned) = &expr.kind {
+            if seen.contains(&&assigned.path) {
+                found.push(expr.start);
+            } else {
+                seen.push(&assigned.path);
+            }
+        }
+
+        shadowed_lets_walk(expr, found);
+    }
+}
+
+/// Recursive walker for [find_shadowed_lets], opening a new scope for each body
+fn shadowed_lets_walk(expr: &Expr, found: &mut Vec<usize>) {
+    match &expr.kind {
+        ExprKind::Body(Body(exprs)) => shadowed_lets_scope(exprs, found),
+        ExprKind::Class(class) => {
+            if let Some(constructor) = &class.constructor {
+                shadowed_lets_scope(&constructor.body, found);
+            }
+
+            shadowed_lets_scope(&class.body, found);
+        }
+        ExprKind::Function(function) => shadowed_lets_scope(&function.body, found),
+        ExprKind::Method(method) => shadowed_lets_scope(&method.body, found),
+        ExprKind::If(cond) => {
+            for seg in cond.segments.iter() {
+                shadowed_lets_walk(&seg.condition, found);
+                shadowed_lets_scope(&seg.body, found);
+            }
+
+            if let Some(IfDefault(body)) = &cond.default {
+                shadowed_lets_scope(body, found);
+            }
+        }
+        ExprKind::While(looped) => {
+            shadowed_lets_walk(&looped.condition, found);
+            shadowed_lets_scope(&looped.body, found);
+        }
+        _ => {
+            for child in expr.children() {
+                shadowed_lets_walk(child, found);
+            }
+        }
+    }
+}
+
+/// Finds the starting index of every `break` which isn't inside of a `while`
+/// body, as there is no loop for it to break out of
+pub fn find_stray_breaks(exprs: &[Expr]) -> Vec<usize> {
+    let mut found = vec![];
+
+    for expr in exprs {
+        stray_breaks_walk(expr, false, &mut found);
+    }
+
+    found
+}
+
+/// Recursive walker for [find_stray_breaks], with `in_loop` being if `expr` is
+/// within a `while` body of the current subprogram
+fn stray_breaks_walk(expr: &Expr, in_loop: bool, found: &mut Vec<usize>) {
+    let in_loop = match &expr.kind {
+        ExprKind::Break if !in_loop => {
+            found.push(expr.start);
+            return;
+        }
+        ExprKind::While(looped) => {
+            stray_breaks_walk(&looped.condition, in_loop, found);
+
+            for child in looped.body.iter() {
+                stray_breaks_walk(child, true, found);
+            }
+
+            return;
+        }
+        ExprKind::Class(_) | ExprKind::Function(_) | ExprKind::Method(_) => false,
+        _ => in_loop,
+    };
+
+    for child in expr.children() {
+        stray_breaks_walk(child, in_loop, found);
+    }
+}
+
+/// Finds the starting index of every arm of `matched` which can never be
+/// reached, either from being after a wildcard `_` arm or from repeating the
+/// literal pattern of an earlier arm. Arms are located by their pattern, or by
+/// their body if their pattern is a wildcard
+pub fn find_unreachable_arms(matched: &Match) -> Vec<usize> {
+    let unreachable = matched.unreachable_segments();
+    let reachable = &matched.segments[..matched.segments.len() - unreachable.len()];
+
+    let mut found = vec![];
+    let mut seen: Vec<&ExprKind> = vec![];
+
+    for seg in reachable {
+        match &seg.pattern {
+            Pattern::Expr(expr) if expr.kind.is_literal() => {
+                if seen.iter().any(|kind| same_literal(kind, &expr.kind)) {
+                    found.push(expr.start);
+                } else {
+                    seen.push(&expr.kind);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    found.extend(unreachable.iter().map(|seg| match &seg.pattern {
+        Pattern::Expr(expr) => expr.start,
+        Pattern::Wildcard => seg.expr.start,
+    }));
+    found
+}
+
+/// Checks if two literals have the same value, ignoring the radix integers were
+/// written in as `0xFF` and `255` are the same integer
+fn same_literal(left: &ExprKind, right: &ExprKind) -> bool {
+    match (left, right) {
+        (ExprKind::IntLit(left), ExprKind::IntLit(right)) => left.0 == right.0,
+        _ => left == right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::parse;
+
+    #[test]
+    fn empty_bodies() {
+        assert_eq!(find_empty_bodies(&parse("fun f() {}").unwrap()), vec![0]);
+        assert_eq!(find_empty_bodies(&parse("class A {}").unwrap()), vec![0]);
+        assert_eq!(
+            find_empty_bodies(&parse("5 while c {} class A { fun g() {} }").unwrap()),
+            vec![2, 23]
+        );
+        assert_eq!(
+            find_empty_bodies(&parse("fun f() { while c { 1 } } class A { let x = 2 }").unwrap()),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn shadowed_lets() {
+        assert_eq!(
+            find_shadowed_lets(&parse("let x = 1 let x = 2").unwrap()),
+            vec![10]
+        );
+        assert_eq!(
+            find_shadowed_lets(&parse("let x = 1 fun f() { let x = 2 let y = x + 1 }").unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            find_shadowed_lets(
+                &parse("while c { let x = 1 let y = 2 let x = 3 } let x = 4").unwrap()
+            ),
+            vec![30]
+        );
+        assert_eq!(
+            find_shadowed_lets(&parse("if c { let x = 1 } else { let x = 2 }").unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            find_shadowed_lets(&parse("let x = 1 + 2 let x = y as Int").unwrap()),
+            vec![14]
+        );
+    }
+
+    /// Shortcut for finding the unreachable arms of a single parsed `match`
+    fn unreachable_arms(input: &str) -> Vec<usize> {
+        match &parse(input).unwrap()[0].kind {
+            ExprKind::Match(matched) => find_unreachable_arms(matched),
+            other => panic!("expected match, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unreachable_match_arms() {
+        assert_eq!(
+            unreachable_arms("match x { 1 => a, _ => b, 2 => c }"),
+            vec![26]
+        );
+        assert_eq!(
+            unreachable_arms("match x { 1 => a, 2 => b, 1 => c }"),
+            vec![26]
+        );
+        assert_eq!(
+            unreachable_arms("match x { _ => a, _ => b, 3 => c }"),
+            vec![23, 26]
+        );
+        assert_eq!(
+            unreachable_arms("match x { 1 => a, y => b, y => c }"),
+            vec![]
+        );
+        assert_eq!(
+            unreachable_arms("match x { 0xFF => a, 255 => b }"),
+            vec![21]
+        );
+    }
+
+    #[test]
+    fn stray_breaks() {
+        assert_eq!(
+            find_stray_breaks(&parse("while true { break }").unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            find_stray_breaks(&parse("break while c { if d { break } }").unwrap()),
+            vec![0]
+        );
+        assert_eq!(
+            find_stray_breaks(&parse("while c { fun f() { break } }").unwrap()),
+            vec![20]
+        );
+    }
+}
@@ -2,5 +2,53 @@
 //! lexing/scanning and parsing
 
 pub mod ast;
+pub mod fold;
 pub mod lexer;
+pub mod lint;
 pub mod parser;
+pub mod print;
+pub mod resolve;
+pub mod symbols;
+
+pub use lexer::Token;
+pub use parser::ParseStop;
+
+use ast::Expr;
+use logos::{Lexer, Logos};
+
+/// Lexes given `input` into a [Token] lexer, shortcut for [Token::lexer]
+pub fn lex(input: &str) -> Lexer<'_, Token> {
+    Token::lexer(input)
+}
+
+/// Lexes and parses given `input` into the resulting expressions, shortcut
+/// for using [parser::launch] with [lex]
+pub fn parse(input: &str) -> Result<Vec<Expr>, ParseStop> {
+    parser::launch(&mut lex(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::{self, ast::*, Token};
+
+    #[test]
+    fn shortcuts() {
+        let mut lex = frontend::lex("let x = 5");
+
+        assert_eq!(lex.next(), Some(Token::Let));
+        assert_eq!(lex.next(), Some(Token::Path(Path::new("x"))));
+        assert_eq!(
+            frontend::parse("5").unwrap(),
+            vec![Expr {
+                kind: IntLit::new(5).into(),
+                doc: None,
+                start: 0,
+                end: 1
+            }]
+        );
+        assert_eq!(
+            frontend::parse("5 +"),
+            Err(frontend::ParseStop::UnexpectedEof("expression"))
+        );
+    }
+}
@@ -1,8 +1,11 @@
 //! Parser for converting lexed tokens into the finalized abstract syntax tree
 
-use super::{ast::*, lexer::Token};
-use logos::Lexer;
-use std::fmt;
+use super::{
+    ast::*,
+    lexer::{self, LexState, Token},
+};
+use logos::{Lexer, Logos, Span};
+use std::{borrow::Cow, fmt};
 
 /// Parsing-specific error/stop enumeration, encompassing the possible errors or
 /// stops in parsing flow which may have occurred during parsing
@@ -11,30 +14,71 @@ pub enum ParseStop {
     //--------//
     // errors //
     //--------//
-    /// Unexpected token
-    UnexpectedToken(String),
+    /// Unexpected token, along with the source slice it was lexed from and the
+    /// index it was found at
+    UnexpectedToken(Token, String, usize),
 
-    /// Unexpected token with a special case in the top level of parsing
-    UnexpectedTokenTop(String),
+    /// Unexpected token with a special case in the top level of parsing, along
+    /// with the index it was found at
+    UnexpectedTokenTop(String, usize),
 
-    /// Unknown token whilst lexing
-    UnknownToken(String),
+    /// Unknown token whilst lexing, along with the index it was found at
+    UnknownToken(String, usize),
 
     /// Operation was found with no lefthand expression
-    NoLeftExpr,
+    NoLeftExpr(OpKind),
 
-    /// File ended unexpectedly
-    UnexpectedEof,
+    /// File ended unexpectedly, along with what was being parsed at the time
+    /// such as `"function parameters"`
+    UnexpectedEof(&'static str),
 
     /// Multiple expressions where given where a single expression should be
     MultipleExpressions,
 
-    /// Multiple default cases where given for a match expression
-    MultipleMatchDefaults,
-
     /// Class names need to be a single identifier, not a path
     ClassNameIsPath,
 
+    /// Function names need to be a single identifier, not a path
+    FunctionNameIsPath,
+
+    /// Comparisons were chained without parenthesis, such as `a < b < c`, which
+    /// is ambiguous as comparisons are non-associative
+    ChainedComparison(OpKind),
+
+    /// Assignment target was an affixed path such as `.x`, which can't be assigned to
+    AffixedAssign,
+
+    /// Equality `==` was used in place of the `=` of a `let`, such as `let x == 5`
+    ExpectedAssign,
+
+    /// Interpolation `${` inside of a string was never closed with a `}`, along
+    /// with the index it was opened at
+    UnterminatedInterpolation(usize),
+
+    /// String was never closed with a `"` before the input ended, along with
+    /// the index of its opening quote
+    UnterminatedString(usize),
+
+    /// Strict equality such as `===` was used, which should be the contained
+    /// operation instead
+    StrictEquality(OpKind),
+
+    /// Class had more than one `init` constructor, along with the index of the
+    /// repeated one
+    DuplicateConstructor(usize),
+
+    /// Function had the same parameter more than once, along with the index of
+    /// the repeated parameter and the index it was first defined at
+    DuplicateParameter(Id, usize, usize),
+
+    /// Lexer stopped advancing, such as by giving a zero-length token, along
+    /// with the index it stalled at
+    LexerStalled(usize),
+
+    /// Token buffer given to [launch_tokens] contained an unknown token, along
+    /// with the span it was given at
+    UnknownBufferedToken(Span),
+
     //---------//
     // special //
     //---------//
@@ -42,28 +86,123 @@ pub enum ParseStop {
     FileEnded,
 }
 
+impl ParseStop {
+    /// Gets the index this stop was found at, for the stops which were caused
+    /// by a specific token
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParseStop::UnexpectedToken(_, _, ind)
+            | ParseStop::UnexpectedTokenTop(_, ind)
+            | ParseStop::UnknownToken(_, ind)
+            | ParseStop::UnterminatedInterpolation(ind)
+            | ParseStop::UnterminatedString(ind)
+            | ParseStop::DuplicateConstructor(ind)
+            | ParseStop::DuplicateParameter(_, ind, _)
+            | ParseStop::LexerStalled(ind) => Some(*ind),
+            ParseStop::UnknownBufferedToken(span) => Some(span.start),
+            _ => None,
+        }
+    }
+
+    /// Maps the index of this stop through `map`, if it has one
+    fn map_offset(self, map: impl Fn(usize) -> usize) -> Self {
+        match self {
+            ParseStop::UnexpectedToken(token, slice, ind) => {
+                ParseStop::UnexpectedToken(token, slice, map(ind))
+            }
+            ParseStop::UnexpectedTokenTop(slice, ind) => {
+                ParseStop::UnexpectedTokenTop(slice, map(ind))
+            }
+            ParseStop::UnknownToken(slice, ind) => ParseStop::UnknownToken(slice, map(ind)),
+            ParseStop::UnterminatedInterpolation(ind) => {
+                ParseStop::UnterminatedInterpolation(map(ind))
+            }
+            ParseStop::UnterminatedString(ind) => ParseStop::UnterminatedString(map(ind)),
+            ParseStop::DuplicateConstructor(ind) => ParseStop::DuplicateConstructor(map(ind)),
+            ParseStop::DuplicateParameter(id, ind, first) => {
+                ParseStop::DuplicateParameter(id, map(ind), map(first))
+            }
+            ParseStop::LexerStalled(ind) => ParseStop::LexerStalled(map(ind)),
+            ParseStop::UnknownBufferedToken(span) => {
+                ParseStop::UnknownBufferedToken(map(span.start)..map(span.end))
+            }
+            other => other,
+        }
+    }
+}
+
 impl fmt::Display for ParseStop {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            ParseStop::UnexpectedToken(slice) | ParseStop::UnexpectedTokenTop(slice) => {
+            ParseStop::UnexpectedToken(token, slice, _) => {
+                write!(f, "Unexpected {} '{}' found", token.kind_name(), slice)
+            }
+            ParseStop::UnexpectedTokenTop(slice, _) => {
                 write!(f, "Unexpected token '{}' found", slice)
             }
-            ParseStop::UnknownToken(slice) => write!(f, "Unknown token '{}' found", slice),
-            ParseStop::NoLeftExpr => {
-                write!(f, "Operation was found with no lefthand expression")
+            ParseStop::UnknownToken(slice, _) => write!(f, "Unknown token '{}' found", slice),
+            ParseStop::NoLeftExpr(kind) => {
+                write!(
+                    f,
+                    "Operation '{}' was found with no lefthand expression",
+                    kind
+                )
+            }
+            ParseStop::UnexpectedEof(while_parsing) => {
+                write!(f, "File ended while parsing {}", while_parsing)
             }
-            ParseStop::UnexpectedEof => write!(f, "File ended unexpectedly"),
             ParseStop::MultipleExpressions => write!(
                 f,
                 "Multiple expressions given where a single expression should be"
             ),
-            ParseStop::MultipleMatchDefaults => write!(
-                f,
-                "Multiple default cases where given for a match expression"
-            ),
             ParseStop::ClassNameIsPath => {
                 write!(f, "Class name is a path and not a single identifier")
             }
+            ParseStop::FunctionNameIsPath => {
+                write!(f, "Function name is a path and not a single identifier")
+            }
+            ParseStop::ChainedComparison(kind) => write!(
+                f,
+                "Comparison '{}' can't be chained, use parenthesis to group comparisons",
+                kind
+            ),
+            ParseStop::AffixedAssign => {
+                write!(
+                    f,
+                    "Assignment target is an affixed path and can't be assigned to"
+                )
+            }
+            ParseStop::ExpectedAssign => {
+                write!(
+                    f,
+                    "Expected '=' to assign to let but found '==', use '=' instead"
+                )
+            }
+            ParseStop::UnterminatedInterpolation(_) => {
+                write!(f, "Interpolation '${{' was never closed with '}}'")
+            }
+            ParseStop::UnterminatedString(_) => {
+                write!(f, "String was never closed with '\"'")
+            }
+            ParseStop::StrictEquality(kind) => write!(
+                f,
+                "Strict equality '{}=' isn't supported, use '{}' instead",
+                kind, kind
+            ),
+            ParseStop::DuplicateConstructor(_) => {
+                write!(f, "Class has more than one 'init' constructor")
+            }
+            ParseStop::DuplicateParameter(id, _, _) => {
+                write!(f, "Parameter '{}' is defined more than once", id)
+            }
+            ParseStop::LexerStalled(_) => {
+                write!(f, "Lexer stopped advancing, please report this as a bug!")
+            }
+            ParseStop::UnknownBufferedToken(span) => write!(
+                f,
+                "Unknown token given at {}..{} of token buffer",
+                span.start, span.end
+            ),
             ParseStop::FileEnded => {
                 write!(f, "File ended expectedly, please report this as a bug!")
             }
@@ -71,15 +210,289 @@ impl fmt::Display for ParseStop {
     }
 }
 
+/// Source of tokens which the parser reads from one at a time, implemented for
+/// a [Lexer] over source code and for a [TokenBuffer] of already-lexed tokens
+pub trait TokenSource: Clone {
+    /// Advances to the next token, giving [None] once the input has ended
+    fn advance(&mut self) -> Option<Token>;
+
+    /// Source [Span] of the token last advanced to, which is empty at the end
+    /// of the final token once the input has ended
+    fn span(&self) -> Span;
+
+    /// Source text of the token last advanced to
+    fn slice(&self) -> Cow<'_, str>;
+
+    /// State shared between the lexer and parser
+    fn extras(&mut self) -> &mut LexState;
+
+    /// Gets the index of the opening quote if the token last advanced to is an
+    /// unterminated string, see [lexer::unterminated_str]
+    fn unterminated_str(&self) -> Option<usize>;
+
+    /// Checks if there's a line break between the token last advanced to and
+    /// the token `ahead` has last advanced to, used to synchronize on new lines
+    fn newline_before(&self, ahead: &Self) -> bool;
+}
+
+impl TokenSource for Lexer<'_, Token> {
+    /// Advances the lexer, giving a single [Token::Error] and then ending if it
+    /// stops progressing so parsing can't loop forever
+    fn advance(&mut self) -> Option<Token> {
+        if self.extras.stalled {
+            return None;
+        }
+
+        let token = self.next()?;
+
+        if !lexer::progresses(self.extras.last_end, &Lexer::span(self)) {
+            self.extras.stalled = true;
+            return Some(Token::Error);
+        }
+
+        self.extras.last_end = Lexer::span(self).end;
+        Some(token)
+    }
+
+    fn span(&self) -> Span {
+        Lexer::span(self)
+    }
+
+    fn slice(&self) -> Cow<'_, str> {
+        Cow::Borrowed(Lexer::slice(self))
+    }
+
+    fn extras(&mut self) -> &mut LexState {
+        &mut self.extras
+    }
+
+    fn unterminated_str(&self) -> Option<usize> {
+        lexer::unterminated_str(self)
+    }
+
+    fn newline_before(&self, ahead: &Self) -> bool {
+        self.source()[Lexer::span(self).end..Lexer::span(ahead).start].contains('\n')
+    }
+}
+
+/// Cursor over a buffer of already-lexed tokens along with the source [Span]
+/// each covers, used as a [TokenSource] by [launch_tokens]. As there's no
+/// source text, the slice of each token is its [Token::lexeme] and no tokens
+/// are seen as starting on a new line
+#[derive(Debug, Clone)]
+pub struct TokenBuffer<'t> {
+    /// Tokens being read from
+    tokens: &'t [(Token, Span)],
+
+    /// Amount of times this has been advanced, stopping one past the end of
+    /// `tokens` once the input has ended
+    pos: usize,
+
+    /// State shared with the parser
+    extras: LexState,
+}
+
+impl<'t> TokenBuffer<'t> {
+    /// Creates a new cursor before the first of `tokens`
+    pub fn new(tokens: &'t [(Token, Span)]) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            extras: LexState::default(),
+        }
+    }
+
+    /// Gets the token last advanced to along with its span, if any
+    fn current(&self) -> Option<&'t (Token, Span)> {
+        self.tokens.get(self.pos.checked_sub(1)?)
+    }
+}
+
+impl TokenSource for TokenBuffer<'_> {
+    fn advance(&mut self) -> Option<Token> {
+        self.pos = (self.pos + 1).min(self.tokens.len() + 1);
+        self.current().map(|(token, _)| token.clone())
+    }
+
+    fn span(&self) -> Span {
+        match (self.current(), self.tokens.last()) {
+            (Some((_, span)), _) => span.clone(),
+            (None, Some((_, last))) if self.pos > 0 => last.end..last.end,
+            _ => 0..0,
+        }
+    }
+
+    fn slice(&self) -> Cow<'_, str> {
+        match self.current() {
+            Some((token, _)) => Cow::Owned(token.lexeme()),
+            None => Cow::Borrowed(""),
+        }
+    }
+
+    fn extras(&mut self) -> &mut LexState {
+        &mut self.extras
+    }
+
+    fn unterminated_str(&self) -> Option<usize> {
+        None // unknown tokens are refused by `launch_tokens` before parsing
+    }
+
+    fn newline_before(&self, _ahead: &Self) -> bool {
+        false
+    }
+}
+
 /// Parses a given lexer input into the resulting parsed values
-pub fn launch(lex: &mut Lexer<Token>) -> Result<Vec<Expr>, ParseStop> {
+pub fn launch<L: TokenSource>(lex: &mut L) -> Result<Vec<Expr>, ParseStop> {
+    Parser::new(lex)
+        .map(|item| item.map_err(|(err, _)| err))
+        .collect()
+}
+
+/// Parses an already-lexed buffer of `tokens` along with the source [Span]
+/// each covers, allowing tokens to be reused or synthetic ones injected. Every
+/// start and error index points into the spans given, and an unknown token
+/// gives an error at its span before parsing
+pub fn launch_tokens(tokens: Vec<(Token, Span)>) -> Result<Vec<Expr>, (ParseStop, usize)> {
+    if let Some((_, span)) = tokens.iter().find(|(token, _)| *token == Token::Error) {
+        return Err((ParseStop::UnknownBufferedToken(span.clone()), span.start));
+    }
+
+    Parser::new(&mut TokenBuffer::new(&tokens)).collect()
+}
+
+/// Parsing session which lazily yields each top-level expression from a lexer
+/// one at a time, allowing streaming instead of collecting like [launch] does
+pub struct Parser<'l, L: TokenSource> {
+    /// Token source being parsed from
+    lex: &'l mut L,
+
+    /// Previous expression which may still become the left of an operation
+    buf: Option<Expr>,
+
+    /// If the end of the lexer or an error has been reached
+    finished: bool,
+}
+
+impl<'l, L: TokenSource> Parser<'l, L> {
+    /// Creates a new parsing session over `lex`
+    pub fn new(lex: &'l mut L) -> Self {
+        Self {
+            lex,
+            buf: None,
+            finished: false,
+        }
+    }
+
+    /// Parses the next top-level expression, giving [None] once the input has
+    /// ended. Errors are given with the index they were found at, after which
+    /// parsing stops
+    pub fn next_item(&mut self) -> Option<Result<Expr, (ParseStop, usize)>> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            let buf_was_some = self.buf.is_some();
+
+            match next(self.lex, &mut self.buf, None, true, false) {
+                Ok(expr) => {
+                    let complete = if buf_was_some { self.buf.take() } else { None };
+
+                    self.buf = Some(expr);
+
+                    if complete.is_some() {
+                        return complete.map(Ok);
+                    }
+                }
+                Err(ParseStop::FileEnded) => {
+                    self.finished = true;
+                    return self.buf.take().map(Ok);
+                }
+                Err(unknown) => {
+                    self.finished = true;
+                    return Some(Err((unknown, self.lex.span().start)));
+                }
+            }
+        }
+    }
+}
+
+impl<L: TokenSource> Iterator for Parser<'_, L> {
+    type Item = Result<Expr, (ParseStop, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_item()
+    }
+}
+
+/// Parses a `fragment` of a larger source such as an interpolated string,
+/// offsetting the start of every parsed expression by `base_offset` so they
+/// point into the original source. Errors are also given with their offset
+/// position in the original source
+pub fn sub_parse(fragment: &str, base_offset: usize) -> Result<Vec<Expr>, (ParseStop, usize)> {
+    let mut lex = Token::lexer(fragment);
+
+    match launch(&mut lex) {
+        Ok(mut exprs) => {
+            for expr in exprs.iter_mut() {
+                offset_starts(expr, base_offset);
+            }
+
+            Ok(exprs)
+        }
+        Err(err) => Err((
+            err.map_offset(|ind| ind + base_offset),
+            lex.span().start + base_offset,
+        )),
+    }
+}
+
+/// Adds `offset` to the start and end of `expr` and all of its children
+fn offset_starts(expr: &mut Expr, offset: usize) {
+    expr.start += offset;
+    expr.end += offset;
+    expr.map_children(|child| offset_starts(child, offset));
+}
+
+/// Synchronization points which [launch_recover] may resume parsing from after
+/// finding an error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncTokens {
+    /// Resumes parsing after the next closing `}` brace
+    pub braces: bool,
+
+    /// Resumes parsing at the next top-level keyword, i.e. `fun`, `pub`, `class`,
+    /// `let`, `if`, `while` or `match`
+    pub keywords: bool,
+
+    /// Resumes parsing at the next token which starts on a new line
+    pub newlines: bool,
+}
+
+/// Default synchronization points used for recovery in [launch_recover]
+pub const SYNC_TOKENS: SyncTokens = SyncTokens {
+    braces: true,
+    keywords: true,
+    newlines: false,
+};
+
+/// Parses a given lexer input like [launch] but recovers from errors, skipping
+/// ahead to the next point allowed by `sync` and carrying on. This returns
+/// every expression which could be parsed along with each error and the index
+/// it was found at
+pub fn launch_recover<L: TokenSource>(
+    lex: &mut L,
+    sync: SyncTokens,
+) -> (Vec<Expr>, Vec<(ParseStop, usize)>) {
     let mut buf = None;
     let mut output = vec![];
+    let mut errors = vec![];
 
     loop {
         let buf_was_some = buf.is_some();
 
-        match next(lex, &mut buf, None, true) {
+        match next(lex, &mut buf, None, true, false) {
             Ok(expr) => {
                 if buf_was_some && buf.is_some() {
                     output.push(buf.take().unwrap());
@@ -88,213 +501,730 @@ pub fn launch(lex: &mut Lexer<Token>) -> Result<Vec<Expr>, ParseStop> {
                 buf = Some(expr);
             }
             Err(ParseStop::FileEnded) => break,
-            Err(unknown) => return Err(unknown.into()),
+            Err(unknown) => {
+                errors.push((unknown, lex.span().start));
+                output.extend(buf.take());
+                synchronize(lex, sync);
+            }
         }
     }
 
-    match buf {
-        Some(expr) => output.push(expr),
-        None => (),
+    output.extend(buf);
+
+    (output, errors)
+}
+
+/// Skips tokens until one of the synchronization points in `sync` is found
+fn synchronize<L: TokenSource>(lex: &mut L, sync: SyncTokens) {
+    if sync.braces && lex.slice() == "}" {
+        return; // the error was found at the brace itself
     }
 
-    Ok(output)
+    loop {
+        let mut ahead = lex.clone();
+
+        match next_token(&mut ahead) {
+            None => break,
+            Some(Token::BraceRight) if sync.braces => {
+                next_token(lex);
+                break;
+            }
+            Some(
+                Token::Fun
+                | Token::Pub
+                | Token::Import
+                | Token::Class
+                | Token::Let
+                | Token::If
+                | Token::While
+                | Token::Match,
+            ) if sync.keywords => break,
+            Some(_) if sync.newlines && lex.newline_before(&ahead) => break,
+            Some(_) => {
+                next_token(lex);
+            }
+        }
+    }
 }
 
 /// Gets the next full expression, used internally as the main parsing hook
-fn next(
-    lex: &mut Lexer<Token>,
+///
+/// If `in_condition` is set, a `{` after the expression begins the body of the
+/// condition it's within rather than a trailing block, as with [call_flow]
+fn next<L: TokenSource>(
+    lex: &mut L,
+    buf: &mut Option<Expr>,
+    doc: Option<String>,
+    is_topmost: bool,
+    in_condition: bool,
+) -> Result<Expr, ParseStop> {
+    let expr = next_expr(lex, buf, doc, is_topmost, in_condition)?;
+
+    Ok(Expr {
+        end: lex.span().end,
+        ..expr
+    })
+}
+
+/// Parses the expression beginning at the next token for [next], which then
+/// ends it at the last token this consumed
+fn next_expr<L: TokenSource>(
+    lex: &mut L,
     buf: &mut Option<Expr>,
     doc: Option<String>,
     is_topmost: bool,
+    in_condition: bool,
 ) -> Result<Expr, ParseStop> {
-    let cur = lex.next();
+    let cur = next_token(lex);
     let start = lex.span().start;
 
     match cur {
-        Some(Token::ParenLeft) => Ok(Expr::from_parse(get_body(lex, ")")?, doc, start)),
-        Some(Token::Op(kind)) => Ok(Expr::from_parse(op_flow(lex, buf, kind)?, doc, start)),
-        Some(Token::Exclaim) => Ok(Expr::from_parse(Not(box_next(lex)?), doc, start)),
+        Some(Token::ParenLeft) => Ok(Expr {
+            doc,
+            start,
+            ..group(get_condition(lex, &[")"])?, true)
+        }),
+        Some(token @ Token::Op(OpKind::PlusEq | OpKind::SubEq)) => Err(unexpected(lex, token)),
+        Some(Token::Op(kind)) => Ok(Expr::from_parse(
+            op_flow(lex, buf, kind, in_condition)?,
+            doc,
+            start,
+        )),
+        Some(Token::Minus) if buf.is_none() => {
+            Ok(Expr::from_parse(neg_flow(lex, in_condition)?, doc, start))
+        }
+        Some(Token::Minus) => Ok(Expr::from_parse(
+            op_flow(lex, buf, OpKind::Sub, in_condition)?,
+            doc,
+            start,
+        )),
+        Some(Token::As) => Ok(Expr::from_parse(cast_flow(lex, buf)?, doc, start)),
+        Some(Token::StrictOp(kind)) => Err(ParseStop::StrictEquality(kind)),
+        Some(Token::Exclaim) => Ok(Expr::from_parse(
+            Not(box_next(lex, in_condition)?),
+            doc,
+            start,
+        )),
         Some(Token::Match) => Ok(Expr::from_parse(match_flow(lex)?, doc, start)),
         Some(Token::True) => Ok(Expr::from_parse(BoolLit(true), doc, start)),
         Some(Token::False) => Ok(Expr::from_parse(BoolLit(false), doc, start)),
         Some(Token::None) => Ok(Expr::from_parse(ExprKind::None, doc, start)),
         Some(Token::Class) => Ok(Expr::from_parse(class_flow(lex)?, doc, start)),
+        Some(Token::If) => Ok(Expr::from_parse(if_flow(lex)?, doc, start)),
         Some(Token::While) => Ok(Expr::from_parse(while_flow(lex)?, doc, start)),
         Some(Token::Break) => Ok(Expr::from_parse(ExprKind::Break, doc, start)),
-        Some(Token::Return) => Ok(Expr::from_parse(Return(box_next(lex)?), doc, start)),
+        Some(Token::Return) => Ok(Expr::from_parse(Return(full_next(lex)?), doc, start)),
         Some(Token::Let) => Ok(Expr::from_parse(let_flow(lex)?, doc, start)),
-        Some(Token::Str(d)) => Ok(Expr::from_parse(StrLit(d), doc, start)),
+        Some(Token::Str(d)) => Ok(Expr::from_parse(str_flow(lex, d)?, doc, start)),
         Some(Token::Char(d)) => Ok(Expr::from_parse(CharLit(d), doc, start)),
         Some(Token::Float(d)) => Ok(Expr::from_parse(FloatLit(d), doc, start)),
-        Some(Token::Int(d)) => Ok(Expr::from_parse(IntLit(d), doc, start)),
-        Some(Token::Doc(d)) => next(lex, buf, Some(d), is_topmost),
+        Some(Token::Int(d)) => Ok(Expr::from_parse(
+            IntLit(d, Radix::of(&lex.slice())),
+            doc,
+            start,
+        )),
+        Some(Token::Doc(d)) => {
+            let found = unexpected(lex, Token::Doc(d.clone()));
+            let expr = next(lex, buf, Some(d), is_topmost, in_condition)?;
+
+            if expr.kind.is_declaration() {
+                Ok(expr)
+            } else {
+                Err(found) // docs can only be attached to declarations
+            }
+        }
         Some(Token::Fun) => Ok(Expr::from_parse(subprogram_flow(lex)?, doc, start)),
-        Some(Token::Path(_d)) => todo!("pathing"),
-        Some(Token::Error) => Err(ParseStop::UnknownToken(lex.slice().to_string())),
-        Some(_) => Err(ParseStop::UnexpectedTokenTop(lex.slice().to_string())),
+        Some(Token::Pub) => Ok(Expr::from_parse(pub_flow(lex)?, doc, start)),
+        Some(Token::Import) => Ok(Expr::from_parse(import_flow(lex)?, doc, start)),
+        Some(Token::Path(path)) if peek(lex) == Some(Token::ParenLeft) => Ok(Expr::from_parse(
+            call_flow(lex, path, in_condition)?,
+            doc,
+            start,
+        )),
+        Some(Token::Path(path)) if peek(lex) == Some(Token::Equals) => {
+            Ok(Expr::from_parse(let_set_flow(lex, path)?, doc, start))
+        }
+        Some(Token::Path(path))
+            if matches!(peek(lex), Some(Token::Op(OpKind::PlusEq | OpKind::SubEq))) =>
+        {
+            Ok(Expr::from_parse(compound_set_flow(lex, path)?, doc, start))
+        }
+        Some(Token::Path(path)) => Ok(Expr::from_parse(LetCall::from(path), doc, start)),
+        Some(Token::Error) if lex.extras().stalled => Err(ParseStop::LexerStalled(start)),
+        Some(Token::Error) if lex.unterminated_str().is_some() => {
+            Err(ParseStop::UnterminatedString(start))
+        }
+        Some(Token::Error) => Err(ParseStop::UnknownToken(
+            lex.slice().to_string(),
+            lex.span().start,
+        )),
+        Some(_) => Err(ParseStop::UnexpectedTokenTop(
+            lex.slice().to_string(),
+            lex.span().start,
+        )),
         None => Err(if is_topmost {
             ParseStop::FileEnded
         } else {
-            ParseStop::UnexpectedEof
+            ParseStop::UnexpectedEof("expression")
         }),
     }
 }
 
-/// Flow for operation grammar, i.e. adding or subtracting
-fn op_flow(lex: &mut Lexer<Token>, buf: &mut Option<Expr>, kind: OpKind) -> Result<Op, ParseStop> {
+/// Flow for operation grammar, i.e. adding or subtracting. Operations are
+/// left-associative apart from `**`, which is right-associative, and
+/// comparisons, which can't be chained, and bind according to [OpKind::precedence]
+fn op_flow<L: TokenSource>(
+    lex: &mut L,
+    buf: &mut Option<Expr>,
+    kind: OpKind,
+    in_condition: bool,
+) -> Result<Op, ParseStop> {
+    let left = buf.take().ok_or(ParseStop::NoLeftExpr(kind))?;
+
+    if let ExprKind::Op(op) = &left.kind {
+        if kind.is_comparison() && op.kind.is_comparison() && !op.grouped {
+            return Err(ParseStop::ChainedComparison(kind));
+        }
+    }
+
     Ok(Op {
-        left: Box::new(buf.take().ok_or(ParseStop::NoLeftExpr)?),
-        right: box_next(lex)?,
+        left: Box::new(left),
+        right: op_right(lex, kind, in_condition)?,
         kind,
+        grouped: false,
     })
 }
 
-/// Flow for `match` conditionals
-fn match_flow(lex: &mut Lexer<Token>) -> Result<Match, ParseStop> {
-    let kind = match lex.next() {
-        Some(Token::Op(op)) => Ok(op),
-        Some(_) => Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
-        None => Err(ParseStop::UnexpectedEof),
-    }?;
-    let condition = Box::new(get_condition(lex, "{")?);
-    let mut segments = vec![];
-    let mut default = None;
+/// Gets the righthand expression of an operation of `kind`, taking any
+/// following operations which bind tighter than `kind` into it so that
+/// `2 + 3 * 4` gives `2 + (3 * 4)`, along with right-associative operations of
+/// the same precedence so that `2 ** 3 ** 2` gives `2 ** (3 ** 2)`
+///
+/// Casts bind tighter than any operation, so the operand directly before an
+/// `as` is cast such as `1 + 2 as Int` giving `1 + (2 as Int)`
+fn op_right<L: TokenSource>(
+    lex: &mut L,
+    kind: OpKind,
+    in_condition: bool,
+) -> Result<Box<Expr>, ParseStop> {
+    let mut right = Some(next(lex, &mut None, None, false, in_condition)?);
+
+    while peek(lex) == Some(Token::As) {
+        next_token(lex);
+        let start = lex.span().start;
+        let cast = cast_flow(lex, &mut right)?;
+
+        right = Some(Expr {
+            end: lex.span().end,
+            ..Expr::from_parse(cast, None, start)
+        });
+    }
+
+    while let Some(next_kind) = peek_op(lex) {
+        let binds_right =
+            next_kind.precedence() == kind.precedence() && next_kind.is_right_associative();
+
+        if next_kind.precedence() < kind.precedence()
+            || (next_kind.precedence() == kind.precedence() && !binds_right)
+        {
+            break;
+        }
+
+        next_token(lex);
+        let start = lex.span().start;
+        let op = op_flow(lex, &mut right, next_kind, in_condition)?;
+
+        right = Some(Expr {
+            end: lex.span().end,
+            ..Expr::from_parse(op, None, start)
+        });
+    }
+
+    Ok(Box::new(right.unwrap()))
+}
+
+/// Flow for unary negation after its `-`, which binds looser than `**` so that
+/// `-2 ** 2` gives `-(2 ** 2)` as in maths, but tighter than other operations
+fn neg_flow<L: TokenSource>(lex: &mut L, in_condition: bool) -> Result<Neg, ParseStop> {
+    let operand = next(lex, &mut None, None, false, in_condition)?;
+
+    if peek_op(lex) != Some(OpKind::Pow) {
+        return Ok(Neg(Box::new(operand)));
+    }
+
+    next_token(lex);
+    let start = lex.span().start;
+    let pow = op_flow(lex, &mut Some(operand), OpKind::Pow, in_condition)?;
+
+    Ok(Neg(Box::new(Expr {
+        end: lex.span().end,
+        ..Expr::from_parse(pow, None, start)
+    })))
+}
+
+/// Flow for function calls after their `path`, i.e. `foo(1, 2)`, with an
+/// optional trailing comma after the final argument
+///
+/// A braced block directly after the call is a trailing block, which is given
+/// as a final [Body] argument such as `each(items) { x }`. These aren't allowed
+/// within conditions like `if f() {}`, where the block is the body instead
+fn call_flow<L: TokenSource>(
+    lex: &mut L,
+    path: Path,
+    in_condition: bool,
+) -> Result<FunctionCall, ParseStop> {
+    ensure(lex, Token::ParenLeft, "function call")?;
+
+    let mut args = call_args(lex)?;
+
+    if !in_condition && peek(lex) == Some(Token::BraceLeft) {
+        next_token(lex);
+
+        let start = lex.span().start;
+        let body = get_body(lex, "}")?;
+
+        args.push(Expr {
+            end: lex.span().end,
+            ..Expr::from_parse(body, None, start)
+        });
+    }
+
+    Ok(FunctionCall { path, args })
+}
+
+/// Gets the arguments of a call for [call_flow] up to and including its `)`
+fn call_args<L: TokenSource>(lex: &mut L) -> Result<Vec<Expr>, ParseStop> {
+    let mut args = vec![];
 
     loop {
-        let (found_seg, should_break) = match_expr(lex)?;
+        if let Some(Token::ParenRight) = peek(lex) {
+            next_token(lex);
+            break;
+        }
 
-        match found_seg {
-            MatchExprReturn::Default(expr) => match default {
-                Some(_) => break Err(ParseStop::MultipleMatchDefaults),
-                None => default = Some(expr),
-            },
-            MatchExprReturn::Segment(seg) => segments.push(seg),
+        args.push(get_condition(lex, &[",", ")"])?);
+
+        if lex.slice() == ")" {
+            break;
+        }
+    }
+
+    Ok(args)
+}
+
+/// Flow for postfix casts, i.e. `x as Int`, converting the previous expression
+fn cast_flow<L: TokenSource>(lex: &mut L, buf: &mut Option<Expr>) -> Result<Cast, ParseStop> {
+    let expr = buf.take().ok_or_else(|| unexpected(lex, Token::As))?;
+
+    match next_token(lex) {
+        Some(Token::Path(ty)) => Ok(Cast {
+            expr: Box::new(expr),
+            ty,
+        }),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("cast")),
+    }
+}
+
+/// Flow for `match` conditionals, defaulting to an equality match if no
+/// operation is given, i.e. `match x {}` being the same as `match == x {}`
+fn match_flow<L: TokenSource>(lex: &mut L) -> Result<Match, ParseStop> {
+    let kind = match peek(lex) {
+        Some(Token::Op(op)) => {
+            next_token(lex);
+            op
         }
+        Some(_) => OpKind::EqEq,
+        None => return Err(ParseStop::UnexpectedEof("match")),
+    };
+    let condition = Box::new(get_condition(lex, &["{"])?);
+    let mut segments = vec![];
+
+    loop {
+        let (segment, should_break) = match_expr(lex)?;
+
+        segments.push(segment);
 
         if should_break {
             break Ok(Match {
                 kind,
                 condition,
                 segments,
-                default,
             });
         }
     }
 }
 
-/// Return for [match__expr], showing the two possible match cases
-enum MatchExprReturn {
-    Segment(MatchSegment),
-    Default(Box<Expr>),
-}
-
 /// Matches a single expression part, i.e. the `<expr> => <expr>` with the bool says if this is the last match segment in a statement
-fn match_expr(lex: &mut Lexer<Token>) -> Result<(MatchExprReturn, bool), ParseStop> {
-    let condition = match get_condition(lex, "=>") {
-        Ok(val) => Some(Box::new(val)),
-        Err(ParseStop::UnexpectedTokenTop(d)) if &d == "_" => {
-            ensure(lex, Token::FatArrow)?;
-            None
-        }
-        Err(unknown) => return Err(unknown),
-    };
+fn match_expr<L: TokenSource>(lex: &mut L) -> Result<(MatchSegment, bool), ParseStop> {
+    let pattern = match_pattern(lex)?;
+
+    if let Some(Token::BraceLeft) = peek(lex) {
+        // braced bodies don't need a comma after them
+        next_token(lex);
+
+        let start = lex.span().start;
+        let expr = Box::new(Expr {
+            end: lex.span().end,
+            ..Expr::from_parse(get_body(lex, "}")?, None, start)
+        });
+
+        if let Some(Token::Comma) = peek(lex) {
+            next_token(lex);
+        }
+
+        let should_break = matches!(peek(lex), Some(Token::BraceRight));
+
+        if should_break {
+            next_token(lex);
+        }
+
+        return Ok((MatchSegment { pattern, expr }, should_break));
+    }
+
     let mut buf = None;
 
     loop {
-        match next(lex, &mut buf, None, false) {
+        match next(lex, &mut buf, None, false, false) {
             Ok(expr) if buf.is_none() => buf = Some(expr),
             Ok(_) => break Err(ParseStop::MultipleExpressions),
-            Err(ParseStop::UnexpectedTokenTop(d)) if buf.is_some() => {
+            Err(ParseStop::UnexpectedTokenTop(d, ind)) if buf.is_some() => {
                 let expr = Box::new(buf.take().unwrap());
                 let should_break = match d.as_str() {
                     "," => false,
                     "}" => true,
-                    _ => return Err(ParseStop::UnexpectedTokenTop(d)),
+                    _ => return Err(ParseStop::UnexpectedTokenTop(d, ind)),
                 };
 
-                break Ok((
-                    match condition {
-                        Some(val) => MatchExprReturn::Segment(MatchSegment {
-                            condition: val,
-                            expr,
-                        }),
-                        None => MatchExprReturn::Default(expr),
-                    },
-                    should_break,
-                ));
+                break Ok((MatchSegment { pattern, expr }, should_break));
             }
             Err(unknown) => break Err(unknown),
         }
     }
 }
 
-/// Flow for `let` grammar
-fn let_flow(lex: &mut Lexer<Token>) -> Result<Let, ParseStop> {
-    let (path, mutable) = match lex.next() {
-        Some(Token::Path(path) )=> Ok((path, false)),
-        Some(Token::Mut) if let Token::Path(path) = lex.next().ok_or(ParseStop::UnexpectedEof)? => Ok((path, true)),
-        Some(_) => Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
-        None => Err(ParseStop::UnexpectedEof)
+/// Gets the pattern of a match segment along with the `=>` following it,
+/// giving [ParseStop::UnexpectedToken] if anything else follows the pattern
+fn match_pattern<L: TokenSource>(lex: &mut L) -> Result<Pattern, ParseStop> {
+    if let Some(Token::Interpret) = peek(lex) {
+        next_token(lex);
+        ensure(lex, Token::FatArrow, "match pattern")?;
+        return Ok(Pattern::Wildcard);
+    }
+
+    let mut buf = None;
+
+    loop {
+        let expr = next(lex, &mut buf, None, false, false)?;
+
+        match peek(lex) {
+            Some(Token::FatArrow) => {
+                next_token(lex);
+                break Ok(Pattern::Expr(Box::new(expr)));
+            }
+            Some(Token::Op(_) | Token::StrictOp(_) | Token::Minus | Token::As) => {
+                buf = Some(expr) // continues the pattern as an operation or cast
+            }
+            Some(token) => {
+                next_token(lex);
+                break Err(unexpected(lex, token));
+            }
+            None => break Err(ParseStop::UnexpectedEof("match pattern")),
+        }
+    }
+}
+
+/// Flow for `let` grammar, which may defer initialization by leaving out the
+/// `= <expr>` such as `let x`
+fn let_flow<L: TokenSource>(lex: &mut L) -> Result<Let, ParseStop> {
+    let (path, mutable) = match next_token(lex) {
+        Some(Token::Path(path)) => Ok((path, false)),
+        Some(Token::Mut) => match next_token(lex) {
+            Some(Token::Path(path)) => Ok((path, true)),
+            Some(token) => Err(unexpected(lex, token)),
+            None => Err(ParseStop::UnexpectedEof("let")),
+        },
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("let")),
     }?;
 
-    ensure(lex, Token::Equals)?;
+    match peek(lex) {
+        Some(Token::Equals) => next_token(lex),
+        Some(Token::Op(OpKind::EqEq)) => {
+            next_token(lex);
+            return Err(ParseStop::ExpectedAssign);
+        }
+        Some(token @ Token::StrictOp(_)) => {
+            next_token(lex);
+            return Err(unexpected(lex, token));
+        }
+        Some(token) if peek_op(lex).is_some() => {
+            next_token(lex);
+            return Err(unexpected(lex, token));
+        }
+        _ => {
+            // deferred initialization such as `let x`
+            return Ok(Let {
+                visibility: Visibility::Private,
+                path,
+                mutable,
+                expr: None,
+            });
+        }
+    };
+
+    if peek(lex).is_none() {
+        return Err(ParseStop::UnexpectedEof("let"));
+    }
 
     Ok(Let {
+        visibility: Visibility::Private,
         path,
         mutable,
-        expr: box_next(lex)?,
+        expr: Some(full_next(lex)?),
     })
 }
 
-/// Flow for `class` objects
-fn class_flow(lex: &mut Lexer<Token>) -> Result<Class, ParseStop> {
-    match lex.next() {
-        Some(Token::Path(path)) => Ok(Class {
-            id: path.to_id().ok_or(ParseStop::ClassNameIsPath)?,
-            body: {
-                ensure(lex, Token::BraceLeft)?;
-                get_body(lex, "}")?
-            },
+/// Flow for reassigning an existing `let` after its `path`, i.e. `x = 5`. This
+/// is right-associative, so `a = b = 5` sets `b` then `a`
+fn let_set_flow<L: TokenSource>(lex: &mut L, path: Path) -> Result<LetSet, ParseStop> {
+    if path.affixed {
+        return Err(ParseStop::AffixedAssign);
+    }
+
+    ensure(lex, Token::Equals, "assignment")?;
+
+    Ok(LetSet {
+        path,
+        expr: full_next(lex)?,
+    })
+}
+
+/// Flow for compound assignments after their `path`, i.e. `x += 1`, which are
+/// desugared into a [LetSet] of the operation such as `x = x + 1`
+fn compound_set_flow<L: TokenSource>(lex: &mut L, path: Path) -> Result<LetSet, ParseStop> {
+    if path.affixed {
+        return Err(ParseStop::AffixedAssign);
+    }
+
+    let target = Expr {
+        end: lex.span().end,
+        ..Expr::from_parse(LetCall::from(path.clone()), None, lex.span().start)
+    };
+    let kind = match next_token(lex) {
+        Some(Token::Op(OpKind::PlusEq)) => OpKind::Plus,
+        Some(Token::Op(OpKind::SubEq)) => OpKind::Sub,
+        _ => unreachable!(), // peeked before flow
+    };
+    let start = lex.span().start;
+    let right = full_next(lex)?;
+
+    Ok(LetSet {
+        path,
+        expr: Box::new(Expr {
+            end: right.end,
+            ..Expr::from_parse(
+                Op {
+                    left: Box::new(target),
+                    right,
+                    kind,
+                    grouped: false,
+                },
+                None,
+                start,
+            )
         }),
-        Some(_) => Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
-        None => Err(ParseStop::UnexpectedEof),
+    })
+}
+
+/// Flow for declarations made public using a leading `pub`, i.e. `pub fun`,
+/// `pub class` or `pub let`
+fn pub_flow<L: TokenSource>(lex: &mut L) -> Result<ExprKind, ParseStop> {
+    let visibility = Visibility::Public;
+
+    match next_token(lex) {
+        Some(Token::Fun) => Ok(Function {
+            visibility,
+            ..subprogram_flow(lex)?
+        }
+        .into()),
+        Some(Token::Class) => Ok(Class {
+            visibility,
+            ..class_flow(lex)?
+        }
+        .into()),
+        Some(Token::Let) => Ok(Let {
+            visibility,
+            ..let_flow(lex)?
+        }
+        .into()),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("public declaration")),
     }
 }
 
-/// Flow for `while` loops
-fn while_flow(lex: &mut Lexer<Token>) -> Result<While, ParseStop> {
-    Ok(While {
-        condition: Box::new(get_condition(lex, "{")?),
-        body: get_body(lex, "}")?,
+/// Flow for `import` statements, which must be given a non-affixed path
+fn import_flow<L: TokenSource>(lex: &mut L) -> Result<Import, ParseStop> {
+    match next_token(lex) {
+        Some(Token::Path(path)) if !path.affixed => Ok(Import(path)),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("import")),
+    }
+}
+
+/// Flow for `class` objects, taking the `init` function in the body as the
+/// class constructor, any other functions in the body as methods and an
+/// optional `: Parent` before the body as the class inherited from
+fn class_flow<L: TokenSource>(lex: &mut L) -> Result<Class, ParseStop> {
+    let id = match next_token(lex) {
+        Some(Token::Path(path)) => path.to_id().ok_or(ParseStop::ClassNameIsPath),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("class")),
+    }?;
+    let parent = if peek(lex) == Some(Token::Colon) {
+        next_token(lex);
+
+        match next_token(lex) {
+            Some(Token::Path(path)) if !path.affixed => Some(path),
+            Some(token) => return Err(unexpected(lex, token)),
+            None => return Err(ParseStop::UnexpectedEof("class parent")),
+        }
+    } else {
+        None
+    };
+
+    ensure(lex, Token::BraceLeft, "class")?;
+
+    let mut constructor = None;
+    let mut constructor_doc = None;
+    let mut body = vec![];
+
+    for expr in get_body(lex, "}")? {
+        match expr.kind {
+            ExprKind::Function(function) if function.path == Path::new("init") => {
+                if constructor.is_some() {
+                    return Err(ParseStop::DuplicateConstructor(expr.start));
+                }
+
+                constructor = Some(function.into());
+                constructor_doc = expr.doc;
+            }
+            ExprKind::Function(function) => body.push(Expr {
+                kind: Method::from(function).into(),
+                ..expr
+            }),
+            kind => body.push(Expr { kind, ..expr }),
+        }
+    }
+
+    Ok(Class {
+        visibility: Visibility::Private,
+        id,
+        parent,
+        constructor,
+        constructor_doc,
+        body,
     })
 }
 
+/// Flow for `if` expressions, including any `else if` or `else` after. Each
+/// `if` may have a single braceless expression as its body in place of a braced
+/// one, such as `if x return 1`
+fn if_flow<L: TokenSource>(lex: &mut L) -> Result<If, ParseStop> {
+    let mut segments = vec![];
+
+    loop {
+        let (condition, braceless) = get_flow_condition(lex, &["{"])?;
+
+        segments.push(IfSegment {
+            condition: Box::new(condition),
+            body: match braceless {
+                Some(expr) => vec![expr],
+                None => get_body(lex, "}")?,
+            },
+        });
+
+        if let Some(Token::Else) = peek(lex) {
+            next_token(lex);
+        } else {
+            break Ok(If {
+                segments,
+                default: None,
+            });
+        }
+
+        match next_token(lex) {
+            Some(Token::If) => continue,
+            Some(Token::BraceLeft) => {
+                break Ok(If {
+                    segments,
+                    default: Some(IfDefault(get_body(lex, "}")?)),
+                })
+            }
+            Some(token) => break Err(unexpected(lex, token)),
+            None => break Err(ParseStop::UnexpectedEof("if")),
+        }
+    }
+}
+
+/// Flow for `while` loops, which may have a single braceless expression as
+/// their body after a colon such as `while x: y`
+fn while_flow<L: TokenSource>(lex: &mut L) -> Result<While, ParseStop> {
+    let condition = match get_flow_condition(lex, &["{", ":"])? {
+        (condition, None) => Box::new(condition),
+        (_, Some(_)) => return Err(ParseStop::MultipleExpressions),
+    };
+    let body = if lex.slice() == ":" {
+        vec![*full_next(lex)?]
+    } else {
+        get_body(lex, "}")?
+    };
+
+    Ok(While { condition, body })
+}
+
 /// Flow for subprograms, i.e. functions and methods
-fn subprogram_flow(lex: &mut Lexer<Token>) -> Result<Function, ParseStop> {
-    let path = match lex.next() {
-        Some(Token::Path(path)) => Ok(path),
-        Some(_) => Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
-        None => Err(ParseStop::UnexpectedEof),
+fn subprogram_flow<L: TokenSource>(lex: &mut L) -> Result<Function, ParseStop> {
+    let path = match next_token(lex) {
+        Some(Token::Path(path)) if path.clone().to_id().is_some() => Ok(path),
+        Some(Token::Path(_)) => Err(ParseStop::FunctionNameIsPath),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("function")),
     }?;
 
-    ensure(lex, Token::ParenLeft)?;
+    ensure(lex, Token::ParenLeft, "function parameters")?;
 
-    let mut args = vec![];
+    let mut args: Vec<Id> = vec![];
+    let mut starts = vec![]; // start of each of the args
+    let mut after_arg = false;
 
     loop {
-        match lex.next().ok_or(ParseStop::UnexpectedEof)? {
-            Token::Path(path) => args.push(
-                path.to_id()
-                    .ok_or(ParseStop::UnexpectedToken(lex.slice().to_string()))?,
-            ),
+        match next_token(lex).ok_or(ParseStop::UnexpectedEof("function parameters"))? {
+            Token::Path(path) => {
+                let id = path
+                    .clone()
+                    .to_id()
+                    .ok_or_else(|| unexpected(lex, Token::Path(path)))?;
+                let start = lex.span().start;
+
+                if let Some(ind) = args.iter().position(|arg| *arg == id) {
+                    return Err(ParseStop::DuplicateParameter(id, start, starts[ind]));
+                }
+
+                args.push(id);
+                starts.push(start);
+                after_arg = true;
+            }
+            Token::Comma if after_arg => after_arg = false, // optional separator
             Token::ParenRight => break,
-            _ => return Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
+            token => return Err(unexpected(lex, token)),
         }
     }
 
-    ensure(lex, Token::BraceLeft)?;
+    ensure(lex, Token::BraceLeft, "function")?;
 
     Ok(Function {
+        visibility: Visibility::Private,
         path,
         args,
         body: get_body(lex, "}")?,
@@ -302,14 +1232,14 @@ fn subprogram_flow(lex: &mut Lexer<Token>) -> Result<Function, ParseStop> {
 }
 
 /// Gets condition which are multiple expression ending with a stray [Token::BraceRight] this consumes, based upon the [launch] function
-fn get_body(lex: &mut Lexer<Token>, stray: &str) -> Result<Vec<Expr>, ParseStop> {
+fn get_body<L: TokenSource>(lex: &mut L, stray: &str) -> Result<Vec<Expr>, ParseStop> {
     let mut buf = None;
     let mut output = vec![];
 
     loop {
         let buf_was_some = buf.is_some();
 
-        match next(lex, &mut buf, None, true) {
+        match next(lex, &mut buf, None, true, false) {
             Ok(expr) => {
                 if buf_was_some && buf.is_some() {
                     output.push(buf.take().unwrap());
@@ -317,61 +1247,346 @@ fn get_body(lex: &mut Lexer<Token>, stray: &str) -> Result<Vec<Expr>, ParseStop>
 
                 buf = Some(expr);
             }
-            Err(ParseStop::UnexpectedTokenTop(d)) if &d == stray => break,
+            Err(ParseStop::UnexpectedTokenTop(d, _)) if d == stray => break,
+            Err(ParseStop::FileEnded) => return Err(ParseStop::UnexpectedEof("body")),
             Err(unknown) => return Err(unknown),
         }
     }
 
-    match buf {
-        Some(expr) => output.push(expr),
-        None => (),
+    if let Some(expr) = buf {
+        output.push(expr)
     }
 
     Ok(output)
 }
 
-/// Gets condition which is a single expression ending with a `stray` token slice this consumes
-fn get_condition(lex: &mut Lexer<Token>, stray: &str) -> Result<Expr, ParseStop> {
+/// Gets condition which is a single expression ending with one of the `strays`
+/// token slices this consumes
+///
+/// Conditions starting with a parenthesized group, such as `while (x) {}`, keep
+/// the starts of the grouped expression so they give the same result as their
+/// paren-free forms
+fn get_condition<L: TokenSource>(lex: &mut L, strays: &[&str]) -> Result<Expr, ParseStop> {
+    match get_open_condition(lex, strays)? {
+        (condition, None) => Ok(condition),
+        (_, Some(_)) => Err(ParseStop::MultipleExpressions),
+    }
+}
+
+/// Gets condition like [get_condition] but allows it to instead end where a
+/// separate expression begins, giving that expression alongside it such as the
+/// `return 1` of `if x return 1`
+fn get_open_condition<L: TokenSource>(
+    lex: &mut L,
+    strays: &[&str],
+) -> Result<(Expr, Option<Expr>), ParseStop> {
+    let in_condition = strays.contains(&"{"); // a `{` here begins the body
     let mut buf = None;
+    let mut lone_group = false;
 
-    loop {
-        match next(lex, &mut buf, None, false) {
-            Ok(expr) if buf.is_none() => buf = Some(expr),
-            Ok(_) => break Err(ParseStop::MultipleExpressions),
-            Err(ParseStop::UnexpectedTokenTop(d)) if buf.is_some() && &d == stray => {
-                break Ok(buf.unwrap())
+    if let Some(Token::ParenLeft) = peek(lex) {
+        next_token(lex);
+        buf = Some(group(get_condition(lex, &[")"])?, true));
+        lone_group = true;
+    }
+
+    let (condition, braceless) = loop {
+        match next(lex, &mut buf, None, false, in_condition) {
+            Ok(expr) if buf.is_none() => {
+                buf = Some(expr);
+                lone_group = false;
             }
-            Err(unknown) => break Err(unknown),
+            Ok(expr) => break (buf.unwrap(), Some(op_tail(lex, expr)?)),
+            Err(ParseStop::UnexpectedTokenTop(d, _)) if buf.is_some() && strays.contains(&&*d) => {
+                break (buf.unwrap(), None)
+            }
+            Err(unknown) => return Err(unknown),
         }
+    };
+
+    // the parenthesis of a lone group such as `while (x) {}` are only around the
+    // condition itself, so it's the same as its paren-free form
+    if lone_group {
+        Ok((group(condition, false), braceless))
+    } else {
+        Ok((condition, braceless))
+    }
+}
+
+/// Gets the condition of an `if` or `while` up to one of the `strays`, allowing
+/// a conditional binding such as `if let x = expr {}` in its place. If another
+/// expression begins before a stray, it's given alongside the condition as with
+/// [get_open_condition]
+fn get_flow_condition<L: TokenSource>(
+    lex: &mut L,
+    strays: &[&str],
+) -> Result<(Expr, Option<Expr>), ParseStop> {
+    if peek(lex) != Some(Token::Let) {
+        return get_open_condition(lex, strays);
+    }
+
+    next_token(lex);
+    let start = lex.span().start;
+
+    let binding = match next_token(lex) {
+        Some(Token::Path(path)) => Ok(path),
+        Some(token) => Err(unexpected(lex, token)),
+        None => Err(ParseStop::UnexpectedEof("conditional let")),
+    }?;
+
+    ensure(lex, Token::Equals, "conditional let")?;
+
+    let (expr, braceless) = get_open_condition(lex, strays)?;
+
+    Ok((
+        Expr {
+            end: expr.end,
+            ..Expr::from_parse(
+                CondLet {
+                    binding,
+                    expr: Box::new(expr),
+                },
+                None,
+                start,
+            )
+        },
+        braceless,
+    ))
+}
+
+/// Sets if `expr` is wrapped in its own parenthesis when it's an [Op], giving it
+/// back
+fn group(mut expr: Expr, grouped: bool) -> Expr {
+    if let ExprKind::Op(op) = &mut expr.kind {
+        op.grouped = grouped;
     }
+
+    expr
 }
 
 /// Gets next expression without passing a previous `buf` of `doc` and returns a
 /// [Box], used as a shortcut for sequential parsing
-fn box_next(lex: &mut Lexer<Token>) -> Result<Box<Expr>, ParseStop> {
-    Ok(Box::new(next(lex, &mut None, None, false)?))
+fn box_next<L: TokenSource>(lex: &mut L, in_condition: bool) -> Result<Box<Expr>, ParseStop> {
+    Ok(Box::new(next(lex, &mut None, None, false, in_condition)?))
+}
+
+/// Gets the next full expression like [box_next] but also takes any operations
+/// or casts following it, used for values such as the `1 + 2` of `let x = 1 + 2`
+fn full_next<L: TokenSource>(lex: &mut L) -> Result<Box<Expr>, ParseStop> {
+    let first = next(lex, &mut None, None, false, false)?;
+    Ok(Box::new(op_tail(lex, first)?))
+}
+
+/// Continues `first` with any operations or casts which follow it, giving the
+/// resulting full expression
+fn op_tail<L: TokenSource>(lex: &mut L, first: Expr) -> Result<Expr, ParseStop> {
+    let mut buf = Some(first);
+
+    while peek(lex) == Some(Token::As) || peek_op(lex).is_some_and(|kind| kind.precedence() > 0) {
+        let expr = next(lex, &mut buf, None, false, false)?;
+        buf = Some(expr);
+    }
+
+    Ok(buf.unwrap())
+}
+
+/// Flow for string literals starting with `string`, merging any string literals
+/// directly following it so that adjacent literals such as `"a" "b"` become a
+/// single `"ab"` literal. Any `${<expr>}` inside gives an [Interpolation]
+fn str_flow<L: TokenSource>(lex: &mut L, string: String) -> Result<ExprKind, ParseStop> {
+    let mut parts = vec![];
+
+    let (contents, offset, raw) = str_contents(lex, string);
+    interpolate(&mut parts, &contents, offset, raw)?;
+
+    while let Some(Token::Str(adjacent)) = peek(lex) {
+        next_token(lex);
+
+        let (contents, offset, raw) = str_contents(lex, adjacent);
+        interpolate(&mut parts, &contents, offset, raw)?;
+    }
+
+    match parts.len() {
+        0 => Ok(StrLit(String::new()).into()),
+        1 if matches!(parts[0].kind, ExprKind::StrLit(_)) => Ok(parts.remove(0).kind),
+        _ => Ok(Interpolation(parts).into()),
+    }
+}
+
+/// Gets the contents of the string literal `string` which was just lexed, along
+/// with the index they start at in the source and if they're the raw contents
+/// of a triple-quoted string. Raw contents haven't been escaped yet, so quotes
+/// inside of their interpolations are kept
+fn str_contents<L: TokenSource>(lex: &L, string: String) -> (String, usize, bool) {
+    let slice = lex.slice();
+    let start = lex.span().start;
+
+    match slice
+        .strip_prefix("\"\"\"")
+        .and_then(|slice| slice.strip_suffix("\"\"\""))
+    {
+        Some(raw) => match raw.strip_prefix('\n') {
+            Some(raw) => (raw.to_string(), start + 4, true),
+            None => (raw.to_string(), start + 3, true),
+        },
+        None => (string, start + 1, false),
+    }
+}
+
+/// Splits the contents of a string literal into its literal parts and the
+/// expressions of any `${<expr>}` interpolations between them, pushing each onto
+/// `parts` with `offset` being the index of `string` within the source. If
+/// `raw`, literal parts are escaped as with [lexer::escape_raw]
+fn interpolate(
+    parts: &mut Vec<Expr>,
+    string: &str,
+    offset: usize,
+    raw: bool,
+) -> Result<(), ParseStop> {
+    let mut literal_start = 0;
+
+    while let Some(found) = string[literal_start..].find("${") {
+        let open = literal_start + found;
+        let close = matching_brace(&string[open + 2..])
+            .map(|ind| open + 2 + ind)
+            .ok_or(ParseStop::UnterminatedInterpolation(offset + open))?;
+
+        push_literal(
+            parts,
+            &string[literal_start..open],
+            offset + literal_start,
+            raw,
+        );
+
+        let mut exprs =
+            sub_parse(&string[open + 2..close], offset + open + 2).map_err(|(err, _)| err)?;
+
+        match exprs.len() {
+            0 => {
+                return Err(ParseStop::UnexpectedToken(
+                    Token::BraceRight,
+                    "}".to_string(),
+                    offset + close,
+                ))
+            }
+            1 => parts.push(exprs.remove(0)),
+            _ => return Err(ParseStop::MultipleExpressions),
+        }
+
+        literal_start = close + 1;
+    }
+
+    push_literal(parts, &string[literal_start..], offset + literal_start, raw);
+    Ok(())
+}
+
+/// Pushes a `literal` part of a string starting at `start` onto `parts`,
+/// merging it into the previous part if that was also a literal. Literals which
+/// are `raw` are escaped first
+fn push_literal(parts: &mut Vec<Expr>, literal: &str, start: usize, raw: bool) {
+    let end = start + literal.len();
+
+    if literal.is_empty() {
+        return;
+    }
+
+    let literal = if raw {
+        lexer::escape_raw(literal)
+    } else {
+        literal.to_string()
+    };
+
+    match parts.last_mut() {
+        Some(Expr {
+            kind: ExprKind::StrLit(StrLit(string)),
+            end: last_end,
+            ..
+        }) => {
+            string.push_str(&literal);
+            *last_end = end;
+        }
+        _ => parts.push(Expr {
+            end,
+            ..Expr::from_parse(StrLit(literal), None, start)
+        }),
+    }
+}
+
+/// Finds the index of the `}` closing an interpolation inside of `input`,
+/// skipping over any nested braced pairs such as the body of an `if`
+fn matching_brace(input: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (ind, c) in input.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(ind),
+            '}' => depth -= 1,
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Creates a [ParseStop::UnexpectedToken] for the `token` which was just lexed
+fn unexpected<L: TokenSource>(lex: &L, token: Token) -> ParseStop {
+    ParseStop::UnexpectedToken(token, lex.slice().to_string(), lex.span().start)
+}
+
+/// Gets the next lex token, skipping over any ordinary comments as they don't
+/// belong to the AST
+fn next_token<L: TokenSource>(lex: &mut L) -> Option<Token> {
+    loop {
+        match lex.advance()? {
+            Token::Comment(_) => continue,
+            token => return Some(token),
+        }
+    }
+}
+
+/// Peeks at the next lex token without consuming it
+fn peek<L: TokenSource>(lex: &L) -> Option<Token> {
+    next_token(&mut lex.clone())
+}
+
+/// Peeks at the next lex token, giving the kind of operation it is if any
+fn peek_op<L: TokenSource>(lex: &L) -> Option<OpKind> {
+    match peek(lex)? {
+        Token::Op(kind) => Some(kind),
+        Token::Minus => Some(OpKind::Sub),
+        _ => None,
+    }
 }
 
-/// Ensures next lex token equals inputted `token` value
-fn ensure(lex: &mut Lexer<Token>, token: Token) -> Result<(), ParseStop> {
-    match lex.next() {
+/// Ensures next lex token equals inputted `token` value, with `while_parsing`
+/// describing what was being parsed if the file ends instead
+fn ensure<L: TokenSource>(
+    lex: &mut L,
+    token: Token,
+    while_parsing: &'static str,
+) -> Result<(), ParseStop> {
+    match next_token(lex) {
         Some(found) if found == token => Ok(()),
-        Some(_) => Err(ParseStop::UnexpectedToken(lex.slice().to_string())),
-        None => Err(ParseStop::UnexpectedEof),
+        Some(found) => Err(unexpected(lex, found)),
+        None => Err(ParseStop::UnexpectedEof(while_parsing)),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use logos::Logos;
-
-    // TODO: basic math
-    // TODO: order of operations
 
     /// Shortcut for parsing next
     fn nparse(input: impl AsRef<str>) -> Expr {
-        next(&mut Token::lexer(input.as_ref()), &mut None, None, true).unwrap()
+        next(
+            &mut Token::lexer(input.as_ref()),
+            &mut None,
+            None,
+            true,
+            false,
+        )
+        .unwrap()
     }
 
     #[test]
@@ -383,13 +1598,15 @@ mod tests {
                     condition: Box::new(Expr {
                         kind: BoolLit(true).into(),
                         doc: None,
-                        start: 6
+                        start: 6,
+                        end: 10
                     }),
                     body: vec![]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 13
             }
         );
         assert_eq!(
@@ -399,17 +1616,20 @@ mod tests {
                     condition: Box::new(Expr {
                         kind: BoolLit(true).into(),
                         doc: None,
-                        start: 6
+                        start: 6,
+                        end: 10
                     }),
                     body: vec![Expr {
                         kind: ExprKind::None,
                         doc: None,
-                        start: 13
+                        start: 13,
+                        end: 17
                     }]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 19
             }
         );
         assert_eq!(
@@ -419,30 +1639,36 @@ mod tests {
                     condition: Box::new(Expr {
                         kind: Op {
                             left: Box::new(Expr {
-                                kind: IntLit(1).into(),
+                                kind: IntLit::new(1).into(),
                                 doc: None,
-                                start: 6
+                                start: 6,
+                                end: 7
                             }),
                             right: Box::new(Expr {
-                                kind: IntLit(2).into(),
+                                kind: IntLit::new(2).into(),
                                 doc: None,
-                                start: 8
+                                start: 8,
+                                end: 9
                             }),
-                            kind: OpKind::Plus
+                            kind: OpKind::Plus,
+                            grouped: false,
                         }
                         .into(),
                         doc: None,
-                        start: 7
+                        start: 7,
+                        end: 9
                     }),
                     body: vec![Expr {
                         kind: ExprKind::None,
                         doc: None,
-                        start: 12
+                        start: 12,
+                        end: 16
                     }]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 18
             }
         );
         assert_eq!(
@@ -452,7 +1678,8 @@ mod tests {
                     condition: Box::new(Expr {
                         kind: BoolLit(true).into(),
                         doc: None,
-                        start: 6
+                        start: 6,
+                        end: 10
                     }),
                     body: vec![
                         Expr {
@@ -460,132 +1687,1903 @@ mod tests {
                                 condition: Box::new(Expr {
                                     kind: BoolLit(true).into(),
                                     doc: None,
-                                    start: 19
+                                    start: 19,
+                                    end: 23
                                 }),
                                 body: vec![
                                     Expr {
                                         kind: ExprKind::None,
                                         doc: None,
-                                        start: 26
+                                        start: 26,
+                                        end: 30
                                     },
                                     Expr {
                                         kind: ExprKind::None,
                                         doc: None,
-                                        start: 31
+                                        start: 31,
+                                        end: 35
                                     }
                                 ]
                             }
                             .into(),
                             doc: None,
-                            start: 13
+                            start: 13,
+                            end: 37
                         },
                         Expr {
                             kind: ExprKind::None,
                             doc: None,
-                            start: 38
+                            start: 38,
+                            end: 42
                         }
                     ]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 44
+            }
+        );
+    }
+
+    #[test]
+    fn while_breaks() {
+        assert_eq!(
+            nparse("while true { break }"),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: BoolLit(true).into(),
+                        doc: None,
+                        start: 6,
+                        end: 10
+                    }),
+                    body: vec![Expr {
+                        kind: ExprKind::Break,
+                        doc: None,
+                        start: 13,
+                        end: 18
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 20
+            }
+        );
+    }
+
+    #[test]
+    fn while_braceless() {
+        assert_eq!(
+            nparse("while x: break"),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: LetCall(Path::new("x")).into(),
+                        doc: None,
+                        start: 6,
+                        end: 7
+                    }),
+                    body: vec![Expr {
+                        kind: ExprKind::Break,
+                        doc: None,
+                        start: 9,
+                        end: 14
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 14
+            }
+        );
+        assert_eq!(
+            nparse("while (a == b): x").kind,
+            nparse("while (a == b) {x}").kind
+        );
+        assert_eq!(
+            nparse("while let x = y: x").kind,
+            nparse("while let x = y {x}").kind
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("while x: a + 1"))
+                .unwrap()
+                .iter()
+                .map(crate::frontend::print::debug_no_spans)
+                .collect::<Vec<_>>(),
+            launch(&mut Token::lexer("while x { a + 1 }"))
+                .unwrap()
+                .iter()
+                .map(crate::frontend::print::debug_no_spans)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            next(&mut Token::lexer("while x:"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedEof("expression"))
+        );
+    }
+
+    #[test]
+    fn while_parens() {
+        assert_eq!(nparse("while (x < 5) {}"), nparse("while  x < 5  {}"));
+        assert_eq!(
+            nparse("while (true) { none }"),
+            nparse("while  true  { none }")
+        );
+        assert_eq!(
+            nparse("while (x < 5) {}"),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: Op {
+                            left: Box::new(Expr {
+                                kind: LetCall::from(Path::new("x")).into(),
+                                doc: None,
+                                start: 7,
+                                end: 8
+                            }),
+                            right: Box::new(Expr {
+                                kind: IntLit::new(5).into(),
+                                doc: None,
+                                start: 11,
+                                end: 12
+                            }),
+                            kind: OpKind::Less,
+                            grouped: false,
+                        }
+                        .into(),
+                        doc: None,
+                        start: 9,
+                        end: 12
+                    }),
+                    body: vec![]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 16
             }
         );
     }
 
     #[test]
-    fn none() {
+    fn negation() {
+        let neg = |expr: Expr, start| Expr {
+            end: expr.end,
+            ..Expr::from_parse(Neg(Box::new(expr)), None, start)
+        };
+
+        assert_eq!(nparse("-5"), neg(int(5, 1), 0));
+        assert_eq!(
+            nparse("-x"),
+            neg(
+                Expr {
+                    kind: LetCall::from(Path::new("x")).into(),
+                    doc: None,
+                    start: 1,
+                    end: 2
+                },
+                0
+            )
+        );
+        assert_eq!(
+            nparse("-(1 + 2)"),
+            neg(
+                Expr {
+                    end: 8,
+                    ..group(op(int(1, 2), OpKind::Plus, int(2, 6), 1), true)
+                },
+                0
+            )
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("-5 + 3")).unwrap(),
+            vec![op(neg(int(5, 1), 0), OpKind::Plus, int(3, 5), 3)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("2 * -3")).unwrap(),
+            vec![op(int(2, 0), OpKind::Mul, neg(int(3, 5), 4), 2)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("5 - 3")).unwrap(),
+            vec![op(int(5, 0), OpKind::Sub, int(3, 4), 2)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("-2 ** 2")).unwrap(),
+            vec![neg(op(int(2, 1), OpKind::Pow, int(2, 6), 3), 0)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("(-2) ** 2")).unwrap(),
+            vec![op(
+                Expr {
+                    start: 0,
+                    end: 4,
+                    ..neg(int(2, 2), 1)
+                },
+                OpKind::Pow,
+                int(2, 8),
+                5
+            )]
+        );
+    }
+
+    #[test]
+    fn wrapper_starts() {
+        assert_eq!(
+            nparse("  !5"),
+            Expr {
+                kind: Not(Box::new(Expr {
+                    kind: IntLit::new(5).into(),
+                    doc: None,
+                    start: 3,
+                    end: 4
+                }))
+                .into(),
+                doc: None,
+                start: 2,
+                end: 4
+            }
+        );
+        assert_eq!(
+            nparse("!!x"),
+            Expr {
+                kind: Not(Box::new(Expr {
+                    kind: Not(Box::new(Expr {
+                        kind: LetCall::from(Path::new("x")).into(),
+                        doc: None,
+                        start: 2,
+                        end: 3
+                    }))
+                    .into(),
+                    doc: None,
+                    start: 1,
+                    end: 3
+                }))
+                .into(),
+                doc: None,
+                start: 0,
+                end: 3
+            }
+        );
+        assert_eq!(
+            nparse("return 5"),
+            Expr {
+                kind: Return(Box::new(Expr {
+                    kind: IntLit::new(5).into(),
+                    doc: None,
+                    start: 7,
+                    end: 8
+                }))
+                .into(),
+                doc: None,
+                start: 0,
+                end: 8
+            }
+        );
+        assert_eq!(
+            nparse("--- doc\n let   x = none"),
+            Expr {
+                kind: Let {
+                    visibility: Visibility::Private,
+                    path: Path::new("x"),
+                    mutable: false,
+                    expr: Some(Box::new(Expr {
+                        kind: ExprKind::None,
+                        doc: None,
+                        start: 19,
+                        end: 23
+                    }))
+                }
+                .into(),
+                doc: Some("doc".to_string()),
+                start: 9,
+                end: 23
+            }
+        );
+        assert_eq!(
+            nparse(" while !x {}"),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: Not(Box::new(Expr {
+                            kind: LetCall::from(Path::new("x")).into(),
+                            doc: None,
+                            start: 8,
+                            end: 9
+                        }))
+                        .into(),
+                        doc: None,
+                        start: 7,
+                        end: 9
+                    }),
+                    body: vec![]
+                }
+                .into(),
+                doc: None,
+                start: 1,
+                end: 12
+            }
+        );
+    }
+
+    #[test]
+    fn ifs() {
+        assert_eq!(
+            nparse("if a { 1 } else if b {} else { 2 3 }"),
+            Expr {
+                kind: If {
+                    segments: vec![
+                        IfSegment {
+                            condition: Box::new(Expr {
+                                kind: LetCall::from(Path::new("a")).into(),
+                                doc: None,
+                                start: 3,
+                                end: 4
+                            }),
+                            body: vec![Expr {
+                                kind: IntLit::new(1).into(),
+                                doc: None,
+                                start: 7,
+                                end: 8
+                            }]
+                        },
+                        IfSegment {
+                            condition: Box::new(Expr {
+                                kind: LetCall::from(Path::new("b")).into(),
+                                doc: None,
+                                start: 19,
+                                end: 20
+                            }),
+                            body: vec![]
+                        }
+                    ],
+                    default: Some(IfDefault(vec![
+                        Expr {
+                            kind: IntLit::new(2).into(),
+                            doc: None,
+                            start: 31,
+                            end: 32
+                        },
+                        Expr {
+                            kind: IntLit::new(3).into(),
+                            doc: None,
+                            start: 33,
+                            end: 34
+                        }
+                    ]))
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 36
+            }
+        );
+        assert_eq!(nparse("if (a) {}"), nparse("if  a  {}"));
+        assert_eq!(
+            next(
+                &mut Token::lexer("if a {} else 5"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedToken(
+                Token::Int(5),
+                "5".to_string(),
+                13
+            ))
+        );
+    }
+
+    #[test]
+    fn if_values() {
+        assert_eq!(
+            nparse("let x = if a { 1 } else { 2 }"),
+            Expr {
+                kind: Let {
+                    visibility: Visibility::Private,
+                    path: Path::new("x"),
+                    mutable: false,
+                    expr: Some(Box::new(Expr {
+                        kind: If {
+                            segments: vec![IfSegment {
+                                condition: Box::new(Expr {
+                                    kind: LetCall::from(Path::new("a")).into(),
+                                    doc: None,
+                                    start: 11,
+                                    end: 12
+                                }),
+                                body: vec![Expr {
+                                    kind: IntLit::new(1).into(),
+                                    doc: None,
+                                    start: 15,
+                                    end: 16
+                                }]
+                            }],
+                            default: Some(IfDefault(vec![Expr {
+                                kind: IntLit::new(2).into(),
+                                doc: None,
+                                start: 26,
+                                end: 27
+                            }]))
+                        }
+                        .into(),
+                        doc: None,
+                        start: 8,
+                        end: 29
+                    }))
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 29
+            }
+        );
+    }
+
+    #[test]
+    fn token_buffers() {
+        let tokens = vec![
+            (Token::Int(5), 0..1),
+            (Token::Op(OpKind::Plus), 2..3),
+            (Token::Int(3), 4..5),
+        ];
+
+        assert_eq!(
+            launch_tokens(tokens),
+            Ok(vec![op(int(5, 0), OpKind::Plus, int(3, 4), 2)])
+        );
+        assert_eq!(
+            launch_tokens(vec![
+                (Token::Let, 10..13),
+                (Token::Path(Path::new("x")), 14..15),
+                (Token::Equals, 16..17),
+                (Token::Char('a' as u32), 18..24),
+            ]),
+            launch(&mut Token::lexer("          let x = '\\x61'")).map_err(|err| (err, 0))
+        );
+        assert_eq!(
+            launch_tokens(vec![
+                (Token::Op(OpKind::Plus), 7..8),
+                (Token::Int(1), 9..10)
+            ]),
+            Err((ParseStop::NoLeftExpr(OpKind::Plus), 7))
+        );
+        assert_eq!(
+            launch_tokens(vec![(Token::Let, 0..3)]),
+            Err((ParseStop::UnexpectedEof("let"), 3))
+        );
+
+        let path = |id| Token::Path(Path::new(id));
+        let comparison = vec![
+            (Token::ParenLeft, 0..0),
+            (path("a"), 0..0),
+            (Token::Op(OpKind::Less), 0..0),
+            (path("b"), 0..0),
+            (Token::ParenRight, 0..0),
+            (Token::Op(OpKind::EqEq), 0..0),
+            (path("c"), 0..0),
+        ];
+
+        assert!(launch_tokens(comparison.clone()).is_ok());
+        assert_eq!(
+            launch_tokens(
+                comparison[1..4]
+                    .iter()
+                    .chain(&comparison[5..])
+                    .cloned()
+                    .collect()
+            ),
+            Err((ParseStop::ChainedComparison(OpKind::EqEq), 0))
+        );
+        assert_eq!(
+            launch_tokens(vec![(Token::Int(1), 0..1), (Token::Error, 2..3)]),
+            Err((ParseStop::UnknownBufferedToken(2..3), 2))
+        );
+        assert_eq!(
+            ParseStop::UnknownBufferedToken(2..3).to_string(),
+            "Unknown token given at 2..3 of token buffer"
+        );
+
+        let tokens = [
+            (Token::Int(1), 0..1),
+            (Token::ParenRight, 4..5),
+            (Token::Let, 8..11),
+            (Token::Path(Path::new("x")), 12..13),
+            (Token::Equals, 14..15),
+            (Token::Int(2), 16..17),
+        ];
+        let (exprs, errors) = launch_recover(&mut TokenBuffer::new(&tokens), SYNC_TOKENS);
+
+        assert_eq!(exprs.len(), 2);
+        assert_eq!((exprs[1].start, exprs[1].end), (8, 17));
+        assert_eq!(
+            errors,
+            vec![(ParseStop::UnexpectedTokenTop(")".to_string(), 4), 4)]
+        );
+    }
+
+    #[test]
+    fn parser_items() {
+        let mut lex = Token::lexer("fun first() {}\nfun second(x) { x }");
+        let mut parser = Parser::new(&mut lex);
+
+        match parser.next_item() {
+            Some(Ok(Expr {
+                kind: ExprKind::Function(function),
+                start: 0,
+                ..
+            })) => assert_eq!(function.path, Path::new("first")),
+            other => panic!("expected first function, found {:?}", other),
+        }
+        match parser.next_item() {
+            Some(Ok(Expr {
+                kind: ExprKind::Function(function),
+                start: 15,
+                ..
+            })) => assert_eq!(function.args, vec!["x".into()]),
+            other => panic!("expected second function, found {:?}", other),
+        }
+        assert_eq!(parser.next_item(), None);
+        assert_eq!(parser.next_item(), None);
+
+        let mut lex = Token::lexer("1 + 2 3 4 fun");
+        let items: Vec<_> = Parser::new(&mut lex).collect();
+
+        assert_eq!(items.len(), 3);
+        assert!(matches!(items[0], Ok(Expr { start: 2, .. })));
+        assert!(matches!(items[1], Ok(Expr { start: 6, .. })));
+        assert_eq!(items[2], Err((ParseStop::UnexpectedEof("function"), 13)));
+    }
+
+    /// Shortcut for building an operation from two expressions
+    fn op(left: Expr, kind: OpKind, right: Expr, start: usize) -> Expr {
+        let end = right.end;
+
+        Expr {
+            kind: Op {
+                left: Box::new(left),
+                right: Box::new(right),
+                kind,
+                grouped: false,
+            }
+            .into(),
+            doc: None,
+            start,
+            end,
+        }
+    }
+
+    /// Shortcut for building an integer literal expression
+    fn int(int: i64, start: usize) -> Expr {
+        Expr {
+            kind: IntLit::new(int).into(),
+            doc: None,
+            start,
+            end: start + int.to_string().len(),
+        }
+    }
+
+    #[test]
+    fn basic_math() {
+        assert_eq!(
+            launch(&mut Token::lexer("1 + 2")).unwrap(),
+            vec![op(int(1, 0), OpKind::Plus, int(2, 4), 2)]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("6 / 3")).unwrap(),
+            vec![op(int(6, 0), OpKind::Div, int(3, 4), 2)]
+        );
+    }
+
+    #[test]
+    fn op_kinds() {
+        let kinds = [
+            ("+", OpKind::Plus),
+            ("-", OpKind::Sub),
+            ("*", OpKind::Mul),
+            ("/", OpKind::Div),
+            ("//", OpKind::FloorDiv),
+            ("**", OpKind::Pow),
+            (">", OpKind::Greater),
+            (">=", OpKind::GreaterEq),
+            ("<", OpKind::Less),
+            ("<=", OpKind::LessEq),
+            ("==", OpKind::EqEq),
+            ("!=", OpKind::NotEq),
+            ("and", OpKind::And),
+            ("or", OpKind::Or),
+        ];
+
+        for (symbol, kind) in kinds {
+            let input = format!("5 {} 3", symbol);
+
+            assert_eq!(
+                launch(&mut Token::lexer(&input)).unwrap(),
+                vec![op(int(5, 0), kind, int(3, 3 + symbol.len()), 2)]
+            );
+        }
+    }
+
+    #[test]
+    fn associativity() {
+        assert_eq!(
+            launch(&mut Token::lexer("1 + 2 + 3")).unwrap(),
+            vec![op(
+                op(int(1, 0), OpKind::Plus, int(2, 4), 2),
+                OpKind::Plus,
+                int(3, 8),
+                6
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("8 / 4 / 2")).unwrap(),
+            vec![op(
+                op(int(8, 0), OpKind::Div, int(4, 4), 2),
+                OpKind::Div,
+                int(2, 8),
+                6
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 / 2 + 3")).unwrap(),
+            vec![op(
+                op(int(1, 0), OpKind::Div, int(2, 4), 2),
+                OpKind::Plus,
+                int(3, 8),
+                6
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 < 2 < 3")),
+            Err(ParseStop::ChainedComparison(OpKind::Less))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 == 2 != 3")),
+            Err(ParseStop::ChainedComparison(OpKind::NotEq))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("while 1 < 2 >= 3 {}")),
+            Err(ParseStop::ChainedComparison(OpKind::GreaterEq))
+        );
+        assert!(launch(&mut Token::lexer("(1 < 2) == true")).is_ok());
+        assert!(launch(&mut Token::lexer("while (1 < 2) == true {}")).is_ok());
+        assert!(launch(&mut Token::lexer("1 < 2 and 2 < 3")).is_ok());
+        assert_eq!(
+            launch(&mut Token::lexer("1 - 2 - 3")).unwrap(),
+            vec![op(
+                op(int(1, 0), OpKind::Sub, int(2, 4), 2),
+                OpKind::Sub,
+                int(3, 8),
+                6
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("2 ** 3 ** 2")).unwrap(),
+            vec![op(
+                int(2, 0),
+                OpKind::Pow,
+                op(int(3, 5), OpKind::Pow, int(2, 10), 7),
+                2
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("2 * 3 ** 2 - 1")).unwrap(),
+            vec![op(
+                op(
+                    int(2, 0),
+                    OpKind::Mul,
+                    op(int(3, 4), OpKind::Pow, int(2, 9), 6),
+                    2
+                ),
+                OpKind::Sub,
+                int(1, 13),
+                11
+            )]
+        );
+    }
+
+    #[test]
+    fn multiplication() {
+        assert_eq!(
+            launch(&mut Token::lexer("2 * 3")).unwrap(),
+            vec![op(int(2, 0), OpKind::Mul, int(3, 4), 2)]
+        );
+        assert_eq!(
+            next(&mut Token::lexer("* 3"), &mut None, None, true, false),
+            Err(ParseStop::NoLeftExpr(OpKind::Mul))
+        );
+    }
+
+    #[test]
+    fn op_chains() {
+        let terms = 2_000;
+        let mut expected = int(1, 0);
+
+        for ind in 1..terms {
+            expected = op(expected, OpKind::Plus, int(1, ind * 4), ind * 4 - 2);
+        }
+
+        assert_eq!(
+            launch(&mut Token::lexer(&vec!["1"; terms].join(" + "))).unwrap(),
+            vec![expected]
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            launch(&mut Token::lexer("2 + 3 * 4")).unwrap(),
+            vec![op(
+                int(2, 0),
+                OpKind::Plus,
+                op(int(3, 4), OpKind::Mul, int(4, 8), 6),
+                2
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("2 * 3 + 4")).unwrap(),
+            vec![op(
+                op(int(2, 0), OpKind::Mul, int(3, 4), 2),
+                OpKind::Plus,
+                int(4, 8),
+                6
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 + 2 * 3 - 4")).unwrap(),
+            vec![op(
+                op(
+                    int(1, 0),
+                    OpKind::Plus,
+                    op(int(2, 4), OpKind::Mul, int(3, 8), 6),
+                    2
+                ),
+                OpKind::Sub,
+                int(4, 12),
+                10
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 < 2 + 3 or 4 == 5 and 6 > 7")).unwrap(),
+            vec![op(
+                op(
+                    int(1, 0),
+                    OpKind::Less,
+                    op(int(2, 4), OpKind::Plus, int(3, 8), 6),
+                    2
+                ),
+                OpKind::Or,
+                op(
+                    op(int(4, 13), OpKind::EqEq, int(5, 18), 15),
+                    OpKind::And,
+                    op(int(6, 24), OpKind::Greater, int(7, 28), 26),
+                    20
+                ),
+                10
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("1 < 2 + 3 < 4")),
+            Err(ParseStop::ChainedComparison(OpKind::Less))
+        );
+    }
+
+    #[test]
+    fn full_values() {
+        assert_eq!(
+            nparse("let x = 1 + 2").kind,
+            Let {
+                visibility: Visibility::Private,
+                path: Path::new("x"),
+                mutable: false,
+                expr: Some(Box::new(op(int(1, 8), OpKind::Plus, int(2, 12), 10)))
+            }
+            .into()
+        );
+        assert_eq!(
+            nparse("return 1 + 2 * 3").kind,
+            Return(Box::new(op(
+                int(1, 7),
+                OpKind::Plus,
+                op(int(2, 11), OpKind::Mul, int(3, 15), 13),
+                9
+            )))
+            .into()
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let x = 1\n2 + 3")).unwrap().len(),
+            2
+        );
+        assert!(matches!(
+            nparse("let x = y as Int + 1").kind,
+            ExprKind::Let(Let { expr: Some(expr), .. }) if matches!(expr.kind, ExprKind::Op(_))
+        ));
+    }
+
+    #[test]
+    fn grouping() {
+        assert_eq!(
+            launch(&mut Token::lexer("(1 + 2) * 3")).unwrap(),
+            vec![op(
+                Expr {
+                    end: 7,
+                    ..group(op(int(1, 1), OpKind::Plus, int(2, 5), 0), true)
+                },
+                OpKind::Mul,
+                int(3, 10),
+                8
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("2 * ((3))")).unwrap(),
+            vec![op(
+                int(2, 0),
+                OpKind::Mul,
+                Expr {
+                    end: 9,
+                    ..int(3, 4)
+                },
+                2
+            )]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("()")),
+            Err(ParseStop::UnexpectedTokenTop(")".to_string(), 1))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("(1 2)")),
+            Err(ParseStop::MultipleExpressions)
+        );
+        assert!(launch(&mut Token::lexer("(1 + 2")).is_err());
+    }
+
+    #[test]
+    fn function_calls() {
+        let call = |args, start, end| Expr {
+            kind: FunctionCall {
+                path: Path::new("foo"),
+                args,
+            }
+            .into(),
+            doc: None,
+            start,
+            end,
+        };
+
+        assert_eq!(nparse("foo()"), call(vec![], 0, 5));
+        assert_eq!(nparse("foo(1)"), call(vec![int(1, 4)], 0, 6));
+        assert_eq!(
+            nparse("foo(1, 2 + 3)"),
+            call(
+                vec![int(1, 4), op(int(2, 7), OpKind::Plus, int(3, 11), 9)],
+                0,
+                13
+            )
+        );
+        assert_eq!(nparse("foo(1,)"), call(vec![int(1, 4)], 0, 7));
+        assert_eq!(
+            launch(&mut Token::lexer("1 + foo(2)")).unwrap(),
+            vec![op(int(1, 0), OpKind::Plus, call(vec![int(2, 8)], 4, 10), 2)]
+        );
+        assert_eq!(
+            next(&mut Token::lexer("foo(1, 2"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedEof("expression"))
+        );
+        assert_eq!(
+            next(&mut Token::lexer("foo(,)"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedTokenTop(",".to_string(), 4))
+        );
+    }
+
+    #[test]
+    fn trailing_blocks() {
+        let path = |id: &str, start: usize| Expr {
+            kind: LetCall(Path::new(id)).into(),
+            doc: None,
+            start,
+            end: start + id.len(),
+        };
+
+        assert_eq!(
+            nparse("each(items) { x }"),
+            Expr {
+                kind: FunctionCall {
+                    path: Path::new("each"),
+                    args: vec![
+                        path("items", 5),
+                        Expr {
+                            kind: Body(vec![path("x", 14)]).into(),
+                            doc: None,
+                            start: 12,
+                            end: 17
+                        }
+                    ]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 17
+            }
+        );
+
+        match nparse("if ready(x) { go() }").kind {
+            ExprKind::If(cond) => {
+                assert!(matches!(
+                    &cond.segments[0].condition.kind,
+                    ExprKind::FunctionCall(call) if call.args.len() == 1
+                ));
+                assert_eq!(cond.segments[0].body.len(), 1);
+            }
+            other => panic!("expected if, found {:?}", other),
+        }
+        match nparse("while ready(f() { x }) { go() }").kind {
+            ExprKind::While(looped) => assert_eq!(looped.body.len(), 1),
+            other => panic!("expected while, found {:?}", other),
+        }
+        match nparse("if !a or ready(x) { go() }").kind {
+            ExprKind::If(cond) => assert_eq!(cond.segments[0].body.len(), 1),
+            other => panic!("expected if, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn let_equality() {
+        let err = next(
+            &mut Token::lexer("let x == 5"),
+            &mut None,
+            None,
+            true,
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(err, ParseStop::ExpectedAssign);
+        assert_eq!(
+            err.to_string(),
+            "Expected '=' to assign to let but found '==', use '=' instead"
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("let mut x == 5"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::ExpectedAssign)
+        );
+    }
+
+    #[test]
+    fn let_sets() {
+        let set = |path, expr: Expr, start| Expr {
+            end: expr.end,
+            ..Expr::from_parse(
+                LetSet {
+                    path,
+                    expr: Box::new(expr),
+                },
+                None,
+                start,
+            )
+        };
+
+        assert_eq!(nparse("x = 10"), set(Path::new("x"), int(10, 4), 0));
+        assert_eq!(
+            nparse("obj.field = 10"),
+            set(
+                Path {
+                    fields: vec!["obj".into()],
+                    id: "field".into(),
+                    affixed: false
+                },
+                int(10, 12),
+                0
+            )
+        );
+        assert_eq!(
+            nparse("a = b = 5"),
+            set(Path::new("a"), set(Path::new("b"), int(5, 8), 4), 0)
+        );
+        assert_eq!(
+            nparse("x = 1 + 2"),
+            set(Path::new("x"), op(int(1, 4), OpKind::Plus, int(2, 8), 6), 0)
+        );
+        assert_eq!(
+            next(&mut Token::lexer(".x = 10"), &mut None, None, true, false),
+            Err(ParseStop::AffixedAssign)
+        );
+        assert!(matches!(nparse("let x = 5").kind, ExprKind::Let(_)));
+    }
+
+    #[test]
+    fn compound_sets() {
+        let x = |start| Expr {
+            end: start + 1,
+            ..Expr::from_parse(LetCall::from(Path::new("x")), None, start)
+        };
+        let compound = |kind, right: Expr| Expr {
+            end: right.end,
+            ..Expr::from_parse(
+                LetSet {
+                    path: Path::new("x"),
+                    expr: Box::new(op(x(0), kind, right, 2)),
+                },
+                None,
+                0,
+            )
+        };
+
+        assert_eq!(nparse("x += 1"), compound(OpKind::Plus, int(1, 5)));
+        assert_eq!(nparse("x -= 10"), compound(OpKind::Sub, int(10, 5)));
+        assert_eq!(
+            nparse("x += 1 + 2"),
+            compound(OpKind::Plus, op(int(1, 5), OpKind::Plus, int(2, 9), 7))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("5 += 1")),
+            Err(ParseStop::UnexpectedToken(
+                Token::Op(OpKind::PlusEq),
+                "+=".to_string(),
+                2
+            ))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer(".x -= 1")),
+            Err(ParseStop::AffixedAssign)
+        );
+    }
+
+    #[test]
+    fn casts() {
+        let x = Expr {
+            kind: LetCall(Path::new("x")).into(),
+            doc: None,
+            start: 0,
+            end: 1,
+        };
+        let as_int = Expr {
+            kind: Cast {
+                expr: Box::new(x.clone()),
+                ty: Path::new("Int"),
+            }
+            .into(),
+            doc: None,
+            start: 2,
+            end: 8,
+        };
+
+        assert_eq!(
+            launch(&mut Token::lexer("x as Int")).unwrap(),
+            vec![as_int.clone()]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("x as Float")).unwrap(),
+            vec![Expr {
+                kind: Cast {
+                    expr: Box::new(x),
+                    ty: Path::new("Float"),
+                }
+                .into(),
+                doc: None,
+                start: 2,
+                end: 10,
+            }]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("x as Int as Float")).unwrap(),
+            vec![Expr {
+                kind: Cast {
+                    expr: Box::new(as_int),
+                    ty: Path::new("Float"),
+                }
+                .into(),
+                doc: None,
+                start: 9,
+                end: 17,
+            }]
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("as Int")),
+            Err(ParseStop::UnexpectedToken(Token::As, "as".to_string(), 0))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("x as 5")),
+            Err(ParseStop::UnexpectedToken(
+                Token::Int(5),
+                "5".to_string(),
+                5
+            ))
+        );
+
+        let b = Expr {
+            kind: LetCall(Path::new("b")).into(),
+            doc: None,
+            start: 4,
+            end: 5,
+        };
+        let b_as_int = Expr {
+            kind: Cast {
+                expr: Box::new(b),
+                ty: Path::new("Int"),
+            }
+            .into(),
+            doc: None,
+            start: 6,
+            end: 12,
+        };
+
+        assert_eq!(
+            launch(&mut Token::lexer("a + b as Int")).unwrap(),
+            vec![op(
+                Expr {
+                    kind: LetCall(Path::new("a")).into(),
+                    doc: None,
+                    start: 0,
+                    end: 1,
+                },
+                OpKind::Plus,
+                b_as_int,
+                2
+            )]
+        );
+        match &launch(&mut Token::lexer("(a + b) as Int")).unwrap()[0].kind {
+            ExprKind::Cast(cast) => assert!(matches!(cast.expr.kind, ExprKind::Op(_))),
+            other => panic!("expected cast, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn no_left_exprs() {
+        for (input, kind) in [
+            ("== 5", OpKind::EqEq),
+            ("/ 5", OpKind::Div),
+            ("+ 5", OpKind::Plus),
+            ("* 5", OpKind::Mul),
+            ("while x { and y }", OpKind::And),
+        ] {
+            let err = launch(&mut Token::lexer(input)).unwrap_err();
+
+            assert_eq!(err, ParseStop::NoLeftExpr(kind));
+            assert_eq!(
+                err.to_string(),
+                format!("Operation '{}' was found with no lefthand expression", kind)
+            );
+        }
+    }
+
+    #[test]
+    fn strict_equality() {
+        let err = launch(&mut Token::lexer("a === b")).unwrap_err();
+
+        assert_eq!(err, ParseStop::StrictEquality(OpKind::EqEq));
+        assert_eq!(
+            err.to_string(),
+            "Strict equality '===' isn't supported, use '==' instead"
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("while a !== b {}"))
+                .unwrap_err()
+                .to_string(),
+            "Strict equality '!==' isn't supported, use '!=' instead"
+        );
+    }
+
+    #[test]
+    fn unexpected_tokens() {
+        let err = next(
+            &mut Token::lexer("let class = 1"),
+            &mut None,
+            None,
+            true,
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseStop::UnexpectedToken(Token::Class, "class".to_string(), 4)
+        );
+        assert_eq!(err.to_string(), "Unexpected keyword 'class' found");
+        assert_eq!(
+            next(
+                &mut Token::lexer("fun x(\"a\")"),
+                &mut None,
+                None,
+                true,
+                false
+            )
+            .unwrap_err()
+            .to_string(),
+            "Unexpected string '\"a\"' found"
+        );
+        assert_eq!(
+            next(&mut Token::lexer("let mut 5"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedToken(
+                Token::Int(5),
+                "5".to_string(),
+                8
+            ))
+        );
+    }
+
+    #[test]
+    fn cond_lets() {
+        assert_eq!(
+            nparse("if let x = foo {}"),
+            Expr {
+                kind: If {
+                    segments: vec![IfSegment {
+                        condition: Box::new(Expr {
+                            kind: CondLet {
+                                binding: Path::new("x"),
+                                expr: Box::new(Expr {
+                                    kind: LetCall(Path::new("foo")).into(),
+                                    doc: None,
+                                    start: 11,
+                                    end: 14
+                                })
+                            }
+                            .into(),
+                            doc: None,
+                            start: 3,
+                            end: 14
+                        }),
+                        body: vec![]
+                    }],
+                    default: None
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 17
+            }
+        );
+        assert_eq!(
+            nparse("while let x = (foo) { x }"),
+            Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: CondLet {
+                            binding: Path::new("x"),
+                            expr: Box::new(Expr {
+                                kind: LetCall(Path::new("foo")).into(),
+                                doc: None,
+                                start: 15,
+                                end: 18
+                            })
+                        }
+                        .into(),
+                        doc: None,
+                        start: 6,
+                        end: 18
+                    }),
+                    body: vec![Expr {
+                        kind: LetCall(Path::new("x")).into(),
+                        doc: None,
+                        start: 22,
+                        end: 23
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 25
+            }
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("if let = foo {}"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedToken(
+                Token::Equals,
+                "=".to_string(),
+                7
+            ))
+        );
+    }
+
+    #[test]
+    fn adjacent_strings() {
+        assert_eq!(
+            nparse("\"a\" \"b\" \"c\""),
+            Expr {
+                kind: StrLit("abc".to_string()).into(),
+                doc: None,
+                start: 0,
+                end: 11
+            }
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("\"a\"\n\"b\" 1")).unwrap(),
+            vec![
+                Expr {
+                    kind: StrLit("ab".to_string()).into(),
+                    doc: None,
+                    start: 0,
+                    end: 7
+                },
+                Expr {
+                    kind: IntLit::new(1).into(),
+                    doc: None,
+                    start: 8,
+                    end: 9
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn interpolations() {
+        let str_lit = |string: &str, start, end| Expr {
+            end,
+            ..Expr::from_parse(StrLit(string.to_string()), None, start)
+        };
+
+        assert_eq!(nparse("\"hello\"").kind, StrLit("hello".to_string()).into());
+        assert_eq!(
+            nparse("\"hello ${name}!\""),
+            Expr {
+                kind: Interpolation(vec![
+                    str_lit("hello ", 1, 7),
+                    Expr {
+                        end: 13,
+                        ..Expr::from_parse(LetCall::from(Path::new("name")), None, 9)
+                    },
+                    str_lit("!", 14, 15)
+                ])
+                .into(),
+                doc: None,
+                start: 0,
+                end: 16
+            }
+        );
+
+        match nparse("\"x ${if a { 1 } else { 2 }}\"").kind {
+            ExprKind::Interpolation(Interpolation(parts)) => {
+                assert_eq!(parts.len(), 2);
+                assert_eq!(parts[0], str_lit("x ", 1, 3));
+                assert!(matches!(parts[1].kind, ExprKind::If(_)));
+            }
+            other => panic!("expected interpolation, found {:?}", other),
+        }
+
+        assert_eq!(
+            nparse("\"a\" \"${b}\""),
+            Expr {
+                kind: Interpolation(vec![
+                    str_lit("a", 1, 2),
+                    Expr {
+                        end: 8,
+                        ..Expr::from_parse(LetCall::from(Path::new("b")), None, 7)
+                    }
+                ])
+                .into(),
+                doc: None,
+                start: 0,
+                end: 10
+            }
+        );
+        assert_eq!(
+            next(&mut Token::lexer("\"a ${b\""), &mut None, None, true, false),
+            Err(ParseStop::UnterminatedInterpolation(3))
+        );
+        assert_eq!(
+            next(&mut Token::lexer("\"${}\""), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedToken(
+                Token::BraceRight,
+                "}".to_string(),
+                3
+            ))
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("\"${1 2}\""),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::MultipleExpressions)
+        );
+
+        let x = |start| Expr {
+            end: start + 1,
+            ..Expr::from_parse(LetCall::from(Path::new("x")), None, start)
+        };
+
+        match nparse("\"\"\"ab ${x}\"\"\"").kind {
+            ExprKind::Interpolation(Interpolation(parts)) => {
+                assert_eq!(parts, vec![str_lit("ab ", 3, 6), x(8)])
+            }
+            other => panic!("expected interpolation, found {:?}", other),
+        }
+        match nparse("\"\"\"\n\"q\" ${x}\"\"\"").kind {
+            ExprKind::Interpolation(Interpolation(parts)) => {
+                assert_eq!(parts, vec![str_lit("\\\"q\\\" ", 4, 8), x(10)])
+            }
+            other => panic!("expected interpolation, found {:?}", other),
+        }
+        match nparse("\"\"\"${f(\"a\")}\"\"\"").kind {
+            ExprKind::Interpolation(Interpolation(parts)) => match &parts[0].kind {
+                ExprKind::FunctionCall(call) => assert_eq!(call.args[0], str_lit("a", 7, 10)),
+                other => panic!("expected call, found {:?}", other),
+            },
+            other => panic!("expected interpolation, found {:?}", other),
+        }
+        assert_eq!(
+            next(
+                &mut Token::lexer("\"\"\"ab ${ ) }\"\"\""),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedTokenTop(")".to_string(), 9))
+        );
+    }
+
+    #[test]
+    fn none() {
+        assert_eq!(
+            nparse("none"),
+            Expr {
+                kind: ExprKind::None,
+                doc: None,
+                start: 0,
+                end: 4
+            }
+        );
+        assert_eq!(
+            nparse("let mynone = none"),
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: false,
+                    path: Path::new("mynone"),
+                    expr: Some(Box::new(Expr {
+                        kind: ExprKind::None,
+                        doc: None,
+                        start: 13,
+                        end: 17
+                    }))
+                }),
+                doc: None,
+                start: 0,
+                end: 17
+            }
+        );
+    }
+
+    #[test]
+    fn lets() {
+        assert_eq!(
+            nparse("let x = 5"),
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: false,
+                    path: Path::new("x"),
+                    expr: Some(Box::new(Expr {
+                        kind: IntLit::new(5).into(),
+                        doc: None,
+                        start: 8,
+                        end: 9
+                    }))
+                }),
+                doc: None,
+                start: 0,
+                end: 9
+            }
+        );
+        assert_eq!(
+            nparse("let mut x = 5"),
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: true,
+                    path: Path::new("x"),
+                    expr: Some(Box::new(Expr {
+                        kind: IntLit::new(5).into(),
+                        doc: None,
+                        start: 12,
+                        end: 13
+                    }))
+                }),
+                doc: None,
+                start: 0,
+                end: 13
+            }
+        );
+        assert_eq!(
+            nparse(r#"let mut blah = "mut""#),
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: true,
+                    path: Path::new("blah"),
+                    expr: Some(Box::new(Expr {
+                        kind: StrLit("mut".into()).into(),
+                        doc: None,
+                        start: 15,
+                        end: 20
+                    }))
+                }),
+                doc: None,
+                start: 0,
+                end: 20
+            }
+        );
+    }
+
+    #[test]
+    fn deferred_lets() {
+        assert_eq!(
+            nparse("let x"),
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: false,
+                    path: Path::new("x"),
+                    expr: None
+                }),
+                doc: None,
+                start: 0,
+                end: 5
+            }
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let mut y\ny = 5")).unwrap()[0],
+            Expr {
+                kind: ExprKind::Let(Let {
+                    visibility: Visibility::Private,
+                    mutable: true,
+                    path: Path::new("y"),
+                    expr: None
+                }),
+                doc: None,
+                start: 0,
+                end: 9
+            }
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("let mut y\ny = 5")).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn documented_declarations() {
+        assert_eq!(nparse("--- doc\nfun f() {}").doc, Some("doc".to_string()));
+        assert_eq!(
+            nparse("--- doc\npub class A {}").doc,
+            Some("doc".to_string())
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("--- doc\n5")),
+            Err(ParseStop::UnexpectedToken(
+                Token::Doc("doc".to_string()),
+                "--- doc".to_string(),
+                0
+            ))
+        );
+        assert!(launch(&mut Token::lexer("fun f() {\n--- doc\nreturn 5\n}")).is_err());
+    }
+
+    #[test]
+    fn imports() {
+        let path = Path {
+            fields: vec!["a".into()],
+            id: "b".into(),
+            affixed: false,
+        };
+
+        assert_eq!(nparse("import a.b").kind, Import(path).into());
+        assert_eq!(
+            launch(&mut Token::lexer("import")),
+            Err(ParseStop::UnexpectedEof("import"))
+        );
+        assert!(matches!(
+            launch(&mut Token::lexer("import .a")),
+            Err(ParseStop::UnexpectedToken(Token::Path(_), _, 7))
+        ));
+    }
+
+    #[test]
+    fn skipped_comments() {
+        assert_eq!(
+            launch(&mut Token::lexer("5 -- five\n+ 3"))
+                .unwrap()
+                .iter()
+                .map(crate::frontend::print::debug_no_spans)
+                .collect::<Vec<_>>(),
+            launch(&mut Token::lexer("5 + 3"))
+                .unwrap()
+                .iter()
+                .map(crate::frontend::print::debug_no_spans)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            launch(&mut Token::lexer(
+                "fun f( -- params\na, -- first\nb) -- sig\n{ -- open\na -- end\n} -- done"
+            ))
+            .map(|exprs| exprs.len()),
+            Ok(1)
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("--- doc\n-- note\nfun f() {}")).unwrap()[0].doc,
+            Some("doc".to_string())
+        );
+    }
+
+    #[test]
+    fn sub_parsing() {
+        assert_eq!(
+            sub_parse("1 + 2", 10),
+            Ok(vec![Expr {
+                kind: Op {
+                    left: Box::new(Expr {
+                        kind: IntLit::new(1).into(),
+                        doc: None,
+                        start: 10,
+                        end: 11
+                    }),
+                    right: Box::new(Expr {
+                        kind: IntLit::new(2).into(),
+                        doc: None,
+                        start: 14,
+                        end: 15
+                    }),
+                    kind: OpKind::Plus,
+                    grouped: false,
+                }
+                .into(),
+                doc: None,
+                start: 12,
+                end: 15
+            }])
+        );
+        assert_eq!(
+            sub_parse("while x { none }", 3),
+            Ok(vec![Expr {
+                kind: While {
+                    condition: Box::new(Expr {
+                        kind: LetCall::from(Path::new("x")).into(),
+                        doc: None,
+                        start: 9,
+                        end: 10
+                    }),
+                    body: vec![Expr {
+                        kind: ExprKind::None,
+                        doc: None,
+                        start: 13,
+                        end: 17
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 3,
+                end: 19
+            }])
+        );
+        assert_eq!(
+            sub_parse("let x + 2", 20),
+            Err((
+                ParseStop::UnexpectedToken(Token::Op(OpKind::Plus), "+".to_string(), 26),
+                26
+            ))
+        );
+    }
+
+    #[test]
+    fn unterminated_strings() {
+        let input = "x = \"unterminated\nmore code";
+
+        assert_eq!(
+            launch(&mut Token::lexer(input)),
+            Err(ParseStop::UnterminatedString(4))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("\"a\\q\"")),
+            Err(ParseStop::UnknownToken("\"a\\q\"".to_string(), 0))
+        );
+    }
+
+    #[test]
+    fn stalled_lexing() {
+        // lexer which appears to go backwards, as if a token were zero-length
+        let stalled = || {
+            let mut lex = Token::lexer("let x = 1\nlet y = 2");
+            lex.extras.last_end = 4;
+            lex
+        };
+
+        assert_eq!(launch(&mut stalled()), Err(ParseStop::LexerStalled(0)));
         assert_eq!(
-            nparse("none"),
-            Expr {
-                kind: ExprKind::None,
-                doc: None,
-                start: 0
+            launch_recover(&mut stalled(), SYNC_TOKENS),
+            (vec![], vec![(ParseStop::LexerStalled(0), 0)])
+        );
+    }
+
+    #[test]
+    fn recovery() {
+        let input = "let = 5 } 7 fun f() {}";
+        let function = Expr {
+            kind: Function {
+                visibility: Visibility::Private,
+                path: Path::new("f"),
+                args: vec![],
+                body: vec![],
             }
+            .into(),
+            doc: None,
+            start: 12,
+            end: 22,
+        };
+        let seven = Expr {
+            kind: IntLit::new(7).into(),
+            doc: None,
+            start: 10,
+            end: 11,
+        };
+
+        assert_eq!(
+            launch_recover(
+                &mut Token::lexer(input),
+                SyncTokens {
+                    braces: false,
+                    keywords: true,
+                    newlines: false
+                }
+            ),
+            (
+                vec![function.clone()],
+                vec![(
+                    ParseStop::UnexpectedToken(Token::Equals, "=".to_string(), 4),
+                    4
+                )]
+            )
         );
         assert_eq!(
-            nparse("let mynone = none"),
-            Expr {
-                kind: ExprKind::Let(Let {
-                    mutable: false,
-                    path: Path::new("mynone"),
-                    expr: Box::new(Expr {
-                        kind: ExprKind::None,
+            launch_recover(
+                &mut Token::lexer(input),
+                SyncTokens {
+                    braces: true,
+                    keywords: false,
+                    newlines: false
+                }
+            ),
+            (
+                vec![seven.clone(), function.clone()],
+                vec![(
+                    ParseStop::UnexpectedToken(Token::Equals, "=".to_string(), 4),
+                    4
+                )]
+            )
+        );
+        assert_eq!(
+            launch_recover(&mut Token::lexer(input), SYNC_TOKENS),
+            (
+                vec![seven, function],
+                vec![(
+                    ParseStop::UnexpectedToken(Token::Equals, "=".to_string(), 4),
+                    4
+                )]
+            )
+        );
+        assert_eq!(
+            launch_recover(
+                &mut Token::lexer("let = 5 6\n7"),
+                SyncTokens {
+                    braces: false,
+                    keywords: false,
+                    newlines: true
+                }
+            ),
+            (
+                vec![Expr {
+                    kind: IntLit::new(7).into(),
+                    doc: None,
+                    start: 10,
+                    end: 11
+                }],
+                vec![(
+                    ParseStop::UnexpectedToken(Token::Equals, "=".to_string(), 4),
+                    4
+                )]
+            )
+        );
+        assert_eq!(
+            launch_recover(&mut Token::lexer("5 } 6"), SYNC_TOKENS),
+            (
+                vec![
+                    Expr {
+                        kind: IntLit::new(5).into(),
                         doc: None,
-                        start: 13
-                    })
-                }),
-                doc: None,
-                start: 0
-            }
+                        start: 0,
+                        end: 1
+                    },
+                    Expr {
+                        kind: IntLit::new(6).into(),
+                        doc: None,
+                        start: 4,
+                        end: 5
+                    }
+                ],
+                vec![(ParseStop::UnexpectedTokenTop("}".to_string(), 2), 2)]
+            )
         );
     }
 
     #[test]
-    fn lets() {
+    fn basic_errs() {
         assert_eq!(
-            nparse("let x = 5"),
-            Expr {
-                kind: ExprKind::Let(Let {
-                    mutable: false,
-                    path: Path::new("x"),
-                    expr: Box::new(Expr {
-                        kind: IntLit(5).into(),
-                        doc: None,
-                        start: 8
-                    })
-                }),
-                doc: None,
-                start: 0
-            }
+            next(&mut Token::lexer("let x + 5"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedToken(
+                Token::Op(OpKind::Plus),
+                "+".to_string(),
+                6
+            ))
         );
         assert_eq!(
-            nparse("let mut x = 5"),
-            Expr {
-                kind: ExprKind::Let(Let {
-                    mutable: true,
-                    path: Path::new("x"),
-                    expr: Box::new(Expr {
-                        kind: IntLit(5).into(),
-                        doc: None,
-                        start: 12
-                    })
-                }),
-                doc: None,
-                start: 0
-            }
+            next(&mut Token::lexer("#"), &mut None, None, true, false),
+            Err(ParseStop::UnknownToken("#".to_string(), 0))
         );
         assert_eq!(
-            nparse(r#"let mut blah = "mut""#),
-            Expr {
-                kind: ExprKind::Let(Let {
-                    mutable: true,
-                    path: Path::new("blah"),
-                    expr: Box::new(Expr {
-                        kind: StrLit("mut".into()).into(),
-                        doc: None,
-                        start: 15
-                    })
-                }),
-                doc: None,
-                start: 0
-            }
+            next(
+                &mut Token::lexer("let x = -- 5"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedEof("let"))
         );
     }
 
     #[test]
-    fn basic_errs() {
+    fn function_names() {
+        assert_eq!(
+            launch(&mut Token::lexer("fun a.b() {}")),
+            Err(ParseStop::FunctionNameIsPath)
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("fun .a() {}")),
+            Err(ParseStop::FunctionNameIsPath)
+        );
+        assert!(matches!(
+            nparse("fun a() {}").kind,
+            ExprKind::Function(Function { path, .. }) if path == Path::new("a")
+        ));
+    }
+
+    #[test]
+    fn eof_contexts() {
+        assert_eq!(
+            launch(&mut Token::lexer("fun f(a, b")),
+            Err(ParseStop::UnexpectedEof("function parameters"))
+        );
         assert_eq!(
-            next(&mut Token::lexer("let x + 5"), &mut None, None, true),
-            Err(ParseStop::UnexpectedToken("+".to_string()))
+            launch(&mut Token::lexer("let x =")),
+            Err(ParseStop::UnexpectedEof("let"))
         );
         assert_eq!(
-            next(&mut Token::lexer("#"), &mut None, None, true),
-            Err(ParseStop::UnknownToken("#".to_string()))
+            launch(&mut Token::lexer("let x = fun f(")),
+            Err(ParseStop::UnexpectedEof("function parameters"))
         );
         assert_eq!(
-            next(&mut Token::lexer("let x = -- 5"), &mut None, None, true),
-            Err(ParseStop::UnexpectedEof)
+            launch(&mut Token::lexer("if a { 1")),
+            Err(ParseStop::UnexpectedEof("body"))
+        );
+        assert_eq!(
+            ParseStop::UnexpectedEof("function parameters").to_string(),
+            "File ended while parsing function parameters"
         );
     }
 
@@ -596,41 +3594,50 @@ mod tests {
             vec![Expr {
                 kind: ExprKind::Op(Op {
                     left: Box::new(Expr {
-                        kind: ExprKind::IntLit(IntLit(5)),
+                        kind: ExprKind::IntLit(IntLit::new(5)),
                         doc: None,
-                        start: 0
+                        start: 0,
+                        end: 1
                     }),
                     right: Box::new(Expr {
-                        kind: ExprKind::IntLit(IntLit(3)),
+                        kind: ExprKind::IntLit(IntLit::new(3)),
                         doc: None,
-                        start: 4
+                        start: 4,
+                        end: 5
                     }),
-                    kind: OpKind::Plus
+                    kind: OpKind::Plus,
+                    grouped: false,
                 }),
                 doc: None,
-                start: 2
+                start: 2,
+                end: 5
             }]
         );
         assert_eq!(
             launch(&mut Token::lexer("!5")).unwrap(),
             vec![Expr {
                 kind: ExprKind::Not(Not(Box::new(Expr {
-                    kind: ExprKind::IntLit(IntLit(5)),
+                    kind: ExprKind::IntLit(IntLit::new(5)),
                     doc: None,
-                    start: 1
+                    start: 1,
+                    end: 2
                 }))),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 2
             }]
         );
-        assert_eq!(launch(&mut Token::lexer("+ 5")), Err(ParseStop::NoLeftExpr));
+        assert_eq!(
+            launch(&mut Token::lexer("+ 5")),
+            Err(ParseStop::NoLeftExpr(OpKind::Plus))
+        );
         assert_eq!(
             launch(&mut Token::lexer("5 +")),
-            Err(ParseStop::UnexpectedEof)
+            Err(ParseStop::UnexpectedEof("expression"))
         );
         assert_eq!(
             launch(&mut Token::lexer("5 + 5 + 5 +")),
-            Err(ParseStop::UnexpectedEof)
+            Err(ParseStop::UnexpectedEof("expression"))
         );
     }
 
@@ -646,15 +3653,17 @@ mod tests {
                 })
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 28
             }]
         );
-        assert_ne!(
+        assert_eq!(
             launch(&mut Token::lexer("hello1_there")).unwrap(),
             vec![Expr {
                 kind: LetCall::from(Path::new("hello1_there")).into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 12
             }]
         );
     }
@@ -666,7 +3675,8 @@ mod tests {
             Ok(vec![Expr {
                 kind: StrLit("hello".to_string()).into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 7
             }])
         );
         assert_eq!(
@@ -674,32 +3684,330 @@ mod tests {
             Ok(vec![Expr {
                 kind: Op {
                     left: Box::new(Expr {
-                        kind: IntLit(56).into(),
+                        kind: IntLit::new(56).into(),
                         doc: None,
-                        start: 0
+                        start: 0,
+                        end: 2
                     }),
                     right: Box::new(Expr {
-                        kind: IntLit(3298).into(),
+                        kind: IntLit::new(3298).into(),
                         doc: None,
-                        start: 8
+                        start: 8,
+                        end: 12
                     }),
-                    kind: OpKind::Plus
+                    kind: OpKind::Plus,
+                    grouped: false,
                 }
                 .into(),
                 doc: None,
-                start: 6
+                start: 6,
+                end: 12
             }])
         );
     }
 
     #[test]
     fn multiple_expressions() {
-        todo!("multiple expressions testing")
+        assert_eq!(
+            launch(&mut Token::lexer("while x y {}")),
+            Err(ParseStop::MultipleExpressions)
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("match x y { 1 => 2 }")),
+            Err(ParseStop::MultipleExpressions)
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("if a b: c")),
+            Err(ParseStop::UnexpectedTokenTop(":".to_string(), 6))
+        );
+    }
+
+    #[test]
+    fn braceless_ifs() {
+        use crate::frontend::print::debug_no_spans;
+
+        let same = |braceless: &str, braced: &str| {
+            let spanless = |input| {
+                launch(&mut Token::lexer(input))
+                    .unwrap()
+                    .iter()
+                    .map(debug_no_spans)
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(spanless(braceless), spanless(braced))
+        };
+
+        same("if x return 1", "if x { return 1 }");
+        same("if x + 1 == 2 return y", "if x + 1 == 2 { return y }");
+        same(
+            "if let x = f() x else if y 2 else { 3 }",
+            "if let x = f() { x } else if y { 2 } else { 3 }",
+        );
+        same(
+            "fun f(x) { if x return 1\nreturn 2 }",
+            "fun f(x) { if x { return 1 } return 2 }",
+        );
+        same("if x y z", "if x { y } z");
+        same("if x return 1 + 2", "if x { return 1 + 2 }");
+        same("if x y + 1 else { z * 2 }", "if x { y + 1 } else { z * 2 }");
+        same("if x y as Int z", "if x { y as Int } z");
+        assert_eq!(nparse("if x return 1").end, 13);
     }
 
     #[test]
     fn matching_basics() {
-        todo!("match statements")
+        assert_eq!(
+            nparse("match == x { 1 => 2, _ => 3 }"),
+            Expr {
+                kind: Match {
+                    kind: OpKind::EqEq,
+                    condition: Box::new(Expr {
+                        kind: LetCall::from(Path::new("x")).into(),
+                        doc: None,
+                        start: 9,
+                        end: 10
+                    }),
+                    segments: vec![
+                        MatchSegment {
+                            pattern: Pattern::Expr(Box::new(Expr {
+                                kind: IntLit::new(1).into(),
+                                doc: None,
+                                start: 13,
+                                end: 14
+                            })),
+                            expr: Box::new(Expr {
+                                kind: IntLit::new(2).into(),
+                                doc: None,
+                                start: 18,
+                                end: 19
+                            })
+                        },
+                        MatchSegment {
+                            pattern: Pattern::Wildcard,
+                            expr: Box::new(Expr {
+                                kind: IntLit::new(3).into(),
+                                doc: None,
+                                start: 26,
+                                end: 27
+                            })
+                        }
+                    ]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 29
+            }
+        );
+        match nparse("match x { 1 => 2 }").kind {
+            ExprKind::Match(matched) => assert_eq!(matched.kind, OpKind::EqEq),
+            _ => panic!("expected match"),
+        }
+        match nparse("match != x { 1 => 2 }").kind {
+            ExprKind::Match(matched) => assert_eq!(matched.kind, OpKind::NotEq),
+            _ => panic!("expected match"),
+        }
+    }
+
+    #[test]
+    fn matching_bools() {
+        let bool_arm = |b, pattern_start, expr: i64, expr_start| MatchSegment {
+            pattern: Pattern::Expr(Box::new(Expr {
+                kind: BoolLit(b).into(),
+                doc: None,
+                start: pattern_start,
+                end: pattern_start + b.to_string().len(),
+            })),
+            expr: Box::new(int(expr, expr_start)),
+        };
+
+        match nparse("match b { true => 1, false => 0 }").kind {
+            ExprKind::Match(matched) => assert_eq!(
+                matched.segments,
+                vec![bool_arm(true, 10, 1, 18), bool_arm(false, 21, 0, 30)]
+            ),
+            _ => panic!("expected match"),
+        }
+        assert_eq!(
+            next(
+                &mut Token::lexer("match b { true 1 }"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedToken(
+                Token::Int(1),
+                "1".to_string(),
+                15
+            ))
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("match b { true { 1 } }"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedToken(
+                Token::BraceLeft,
+                "{".to_string(),
+                15
+            ))
+        );
+    }
+
+    #[test]
+    fn matching_wildcards() {
+        assert_eq!(
+            nparse("match x { 1 => {} _ => {} }"),
+            Expr {
+                kind: Match {
+                    kind: OpKind::EqEq,
+                    condition: Box::new(Expr {
+                        kind: LetCall::from(Path::new("x")).into(),
+                        doc: None,
+                        start: 6,
+                        end: 7
+                    }),
+                    segments: vec![
+                        MatchSegment {
+                            pattern: Pattern::Expr(Box::new(Expr {
+                                kind: IntLit::new(1).into(),
+                                doc: None,
+                                start: 10,
+                                end: 11
+                            })),
+                            expr: Box::new(Expr {
+                                kind: Body(vec![]).into(),
+                                doc: None,
+                                start: 15,
+                                end: 16
+                            })
+                        },
+                        MatchSegment {
+                            pattern: Pattern::Wildcard,
+                            expr: Box::new(Expr {
+                                kind: Body(vec![]).into(),
+                                doc: None,
+                                start: 23,
+                                end: 24
+                            })
+                        }
+                    ]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 27
+            }
+        );
+
+        match nparse("match x { 1 => {} _ => {} }").kind {
+            ExprKind::Match(matched) => assert!(matched.unreachable_segments().is_empty()),
+            _ => panic!("expected match"),
+        }
+        match nparse("match x { _ => 1, 2 => 3, 4 => {} }").kind {
+            ExprKind::Match(matched) => {
+                assert_eq!(matched.unreachable_segments().len(), 2);
+                assert_eq!(
+                    crate::frontend::lint::find_unreachable_arms(&matched),
+                    vec![18, 26]
+                );
+            }
+            _ => panic!("expected match"),
+        }
+    }
+
+    #[test]
+    fn visibility() {
+        assert_eq!(
+            nparse("pub fun f() {}"),
+            Expr {
+                kind: Function {
+                    visibility: Visibility::Public,
+                    path: Path::new("f"),
+                    args: vec![],
+                    body: vec![]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 14
+            }
+        );
+        assert_eq!(
+            nparse("fun g() {}"),
+            Expr {
+                kind: Function {
+                    visibility: Visibility::Private,
+                    path: Path::new("g"),
+                    args: vec![],
+                    body: vec![]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 10
+            }
+        );
+
+        for (input, expected) in [
+            ("pub class C {}", Visibility::Public),
+            ("class C {}", Visibility::Private),
+            ("pub let mut x = 1", Visibility::Public),
+            ("--- Docs\npub let x = 1", Visibility::Public),
+        ] {
+            match nparse(input).kind {
+                ExprKind::Class(class) => assert_eq!(class.visibility, expected),
+                ExprKind::Let(assigned) => assert_eq!(assigned.visibility, expected),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+
+        assert_eq!(
+            next(&mut Token::lexer("pub 5"), &mut None, None, true, false),
+            Err(ParseStop::UnexpectedToken(
+                Token::Int(5),
+                "5".to_string(),
+                4
+            ))
+        );
+    }
+
+    #[test]
+    fn nested_docs() {
+        assert_eq!(
+            launch(&mut Token::lexer(
+                "--- Outer\nfun outer() {\n    --- Inner\n    fun inner() {}\n}"
+            ))
+            .unwrap(),
+            vec![Expr {
+                kind: Function {
+                    visibility: Visibility::Private,
+                    path: Path::new("outer"),
+                    args: vec![],
+                    body: vec![Expr {
+                        kind: Function {
+                            visibility: Visibility::Private,
+                            path: Path::new("inner"),
+                            args: vec![],
+                            body: vec![]
+                        }
+                        .into(),
+                        doc: Some("Inner".to_string()),
+                        start: 42,
+                        end: 56
+                    }]
+                }
+                .into(),
+                doc: Some("Outer".to_string()),
+                start: 10,
+                end: 58
+            }]
+        );
     }
 
     #[test]
@@ -708,13 +4016,15 @@ mod tests {
             launch(&mut Token::lexer("fun main() {}")).unwrap(),
             vec![Expr {
                 kind: Function {
+                    visibility: Visibility::Private,
                     path: Path::new("main"),
                     args: vec![],
                     body: vec![]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 13
             }]
         );
 
@@ -722,57 +4032,67 @@ mod tests {
             launch(&mut Token::lexer("fun main() { 1 'c' }")).unwrap(),
             vec![Expr {
                 kind: Function {
+                    visibility: Visibility::Private,
                     path: Path::new("main"),
                     args: vec![],
                     body: vec![
                         Expr {
-                            kind: ExprKind::IntLit(IntLit(1)),
+                            kind: ExprKind::IntLit(IntLit::new(1)),
                             doc: None,
-                            start: 13
+                            start: 13,
+                            end: 14
                         },
                         Expr {
                             kind: ExprKind::CharLit(CharLit('c' as u32)),
                             doc: None,
-                            start: 15
+                            start: 15,
+                            end: 18
                         }
                     ]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 20
             }]
         );
 
         let sixnine_plus_two = Expr {
             kind: Op {
                 left: Box::new(Expr {
-                    kind: IntLit(69).into(),
+                    kind: IntLit::new(69).into(),
                     doc: None,
                     start: 20,
+                    end: 22,
                 }),
                 right: Box::new(Expr {
-                    kind: IntLit(2).into(),
+                    kind: IntLit::new(2).into(),
                     doc: None,
                     start: 25,
+                    end: 26,
                 }),
                 kind: OpKind::Plus,
+                grouped: false,
             }
             .into(),
             doc: None,
             start: 23,
+            end: 26,
         };
 
         assert_eq!(
             launch(&mut Token::lexer("fun hello_there() { 69 + 2 }")).unwrap(),
             vec![Expr {
                 kind: Function {
-                    path: Path::new("hello_there").into(),
+                    visibility: Visibility::Private,
+                    path: Path::new("hello_there"),
                     args: vec![],
                     body: vec![sixnine_plus_two]
                 }
                 .into(),
                 doc: None,
-                start: 0
+                start: 0,
+                end: 28
             }]
         );
     }
@@ -781,49 +4101,62 @@ mod tests {
     fn classes() {
         let y = Expr {
             kind: ExprKind::Let(Let {
+                visibility: Visibility::Private,
                 path: Path::new("y"),
                 mutable: true,
-                expr: Box::new(Expr {
-                    kind: IntLit(4).into(),
+                expr: Some(Box::new(Expr {
+                    kind: IntLit::new(4).into(),
                     doc: None,
                     start: 62,
-                }),
+                    end: 63,
+                })),
             }),
             doc: None,
             start: 50,
+            end: 63,
         };
 
         let other_thing = Expr {
-            kind: ExprKind::Function(Function {
+            kind: ExprKind::Method(Method {
+                visibility: Visibility::Private,
                 path: Path::new("other_thing"),
                 args: vec![Id("x".to_string())],
                 body: vec![y],
             }),
             doc: None,
             start: 29,
+            end: 65,
         };
 
         let x = Expr {
             kind: ExprKind::Let(Let {
+                visibility: Visibility::Private,
                 path: Path::new("x"),
                 mutable: false,
-                expr: Box::new(Expr {
-                    kind: IntLit(2).into(),
+                expr: Some(Box::new(Expr {
+                    kind: IntLit::new(2).into(),
                     doc: None,
                     start: 27,
-                }),
+                    end: 28,
+                })),
             }),
             doc: None,
             start: 19,
+            end: 28,
         };
 
         let hello_there = Expr {
             kind: ExprKind::Class(Class {
+                visibility: Visibility::Private,
                 id: Id("HelloThere".to_string()),
+                parent: None,
+                constructor: None,
+                constructor_doc: None,
                 body: vec![x, other_thing],
             }),
             doc: None,
             start: 0,
+            end: 67,
         };
 
         assert_eq!(
@@ -831,4 +4164,134 @@ mod tests {
             hello_there
         );
     }
+
+    #[test]
+    fn class_methods() {
+        let methods = match nparse("class A { fun a(self) {} pub fun b(x) {} }").kind {
+            ExprKind::Class(class) => class.body,
+            other => panic!("expected class, found {:?}", other),
+        };
+
+        match (&methods[0].kind, &methods[1].kind) {
+            (ExprKind::Method(a), ExprKind::Method(b)) => {
+                assert!(a.takes_self());
+                assert!(!b.takes_self());
+                assert_eq!(b.visibility, Visibility::Public);
+            }
+            other => panic!("expected methods, found {:?}", other),
+        }
+        assert!(matches!(
+            nparse("fun f(self) {}").kind,
+            ExprKind::Function(_)
+        ));
+    }
+
+    #[test]
+    fn class_parents() {
+        let parent = |input| match nparse(input).kind {
+            ExprKind::Class(class) => class.parent,
+            other => panic!("expected class, found {:?}", other),
+        };
+
+        assert_eq!(parent("class Dog : Animal {}"), Some(Path::new("Animal")));
+        assert_eq!(parent("class Dog {}"), None);
+        assert_eq!(
+            parent("class Dog : animals.Animal {}"),
+            Some(Path {
+                fields: vec!["animals".into()],
+                id: "Animal".into(),
+                affixed: false
+            })
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("class Dog :")),
+            Err(ParseStop::UnexpectedEof("class parent"))
+        );
+        assert_eq!(
+            launch(&mut Token::lexer("class Dog : {}")),
+            Err(ParseStop::UnexpectedToken(
+                Token::BraceLeft,
+                "{".to_string(),
+                12
+            ))
+        );
+    }
+
+    #[test]
+    fn class_constructors() {
+        assert_eq!(
+            nparse("class Foo { fun init(self, x) {} fun bar() {} }"),
+            Expr {
+                kind: Class {
+                    visibility: Visibility::Private,
+                    id: Id("Foo".to_string()),
+                    parent: None,
+                    constructor: Some(Method {
+                        visibility: Visibility::Private,
+                        path: Path::new("init"),
+                        args: vec!["self".into(), "x".into()],
+                        body: vec![]
+                    }),
+                    constructor_doc: None,
+                    body: vec![Expr {
+                        kind: Method {
+                            visibility: Visibility::Private,
+                            path: Path::new("bar"),
+                            args: vec![],
+                            body: vec![]
+                        }
+                        .into(),
+                        doc: None,
+                        start: 33,
+                        end: 45
+                    }]
+                }
+                .into(),
+                doc: None,
+                start: 0,
+                end: 47
+            }
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("class A { fun init() {} fun init(x) {} }"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::DuplicateConstructor(24))
+        );
+
+        let documented = nparse("class A {\n--- Makes an A\nfun init(self) {}\n}");
+
+        match &documented.kind {
+            ExprKind::Class(class) => {
+                assert_eq!(class.constructor_doc, Some("Makes an A".to_string()))
+            }
+            other => panic!("expected class, found {:?}", other),
+        }
+        assert!(crate::frontend::print::to_source(&[documented])
+            .contains("--- Makes an A\n    fun init(self) {}"));
+        assert_eq!(
+            next(
+                &mut Token::lexer("fun f(a, b a) {}"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::DuplicateParameter("a".into(), 11, 6))
+        );
+        assert_eq!(
+            next(
+                &mut Token::lexer("fun f(a,, b) {}"),
+                &mut None,
+                None,
+                true,
+                false
+            ),
+            Err(ParseStop::UnexpectedToken(Token::Comma, ",".to_string(), 8))
+        );
+    }
 }
@@ -0,0 +1,780 @@
+//! Printing stage for turning parsed [Expr] nodes back into source code, used
+//! for formatting and source-to-source tooling
+
+use super::{
+    ast::*,
+    lexer::{self, Token},
+};
+use logos::Logos;
+use std::{collections::HashMap, ops::Range};
+
+/// Amount of spaces used per level of indentation
+const INDENT: &str = "    ";
+
+/// Renders given expressions back into normalized Jingo source code, with each
+/// top-level expression on a new line
+pub fn to_source(exprs: &[Expr]) -> String {
+    exprs
+        .iter()
+        .map(|expr| format!("{}\n", render(expr, 0, None)))
+        .collect()
+}
+
+/// Renders given expressions back into source code whilst preserving the
+/// original `source` verbatim for any subtree which is unchanged from the
+/// `original` expressions `source` parsed into, only re-rendering the nodes
+/// which differ. Subtrees are matched up by their spans, so changed nodes should
+/// keep the spans they were parsed with and new nodes should be given spans
+/// which aren't in `source`
+///
+/// Spacing, comments and documentation between unchanged top-level expressions
+/// is kept byte-for-byte, so reprinting an unmodified parse gives back the
+/// original input
+pub fn reprint_preserving(source: &str, original: &[Expr], exprs: &[Expr]) -> String {
+    let keep = Preserved::new(source, original);
+    let regions = get_regions(source, original);
+
+    let mut output = String::new();
+    let mut cursor = 0;
+    let mut last_end = 0;
+
+    for expr in exprs {
+        match original
+            .iter()
+            .zip(regions.iter())
+            .position(|(orig, range)| orig == expr && range.start >= cursor)
+        {
+            Some(ind) => {
+                let range = &regions[ind];
+                let gap_start = if ind == 0 { 0 } else { regions[ind - 1].end };
+
+                output.push_str(&source[gap_start.max(last_end)..range.end]);
+                cursor = range.end;
+                last_end = range.end;
+            }
+            None => {
+                if !output.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+
+                output.push_str(render(expr, 0, Some(&keep)).as_str());
+            }
+        }
+    }
+
+    match regions.last() {
+        Some(range) if last_end == range.end => output.push_str(&source[range.end..]),
+        _ if !output.is_empty() => output.push('\n'),
+        _ => (),
+    }
+
+    output
+}
+
+/// Original source along with every node it parsed into, used by
+/// [reprint_preserving] to find the source of unchanged subtrees
+struct Preserved<'a> {
+    /// Source which was parsed
+    source: &'a str,
+
+    /// Tokens of the source, used to find the documentation before a node
+    tokens: Vec<(Token, Range<usize>)>,
+
+    /// Every parsed node keyed by its start and end
+    nodes: HashMap<(usize, usize), Vec<&'a Expr>>,
+}
+
+impl<'a> Preserved<'a> {
+    /// Indexes every node of the `original` parse of `source`
+    fn new(source: &'a str, original: &'a [Expr]) -> Self {
+        fn index<'a>(nodes: &mut HashMap<(usize, usize), Vec<&'a Expr>>, expr: &'a Expr) {
+            nodes.entry((expr.start, expr.end)).or_default().push(expr);
+            expr.children()
+                .into_iter()
+                .for_each(|child| index(nodes, child));
+        }
+
+        let mut nodes = HashMap::new();
+        original.iter().for_each(|expr| index(&mut nodes, expr));
+
+        Self {
+            source,
+            tokens: Token::lexer(source).spanned().collect(),
+            nodes,
+        }
+    }
+
+    /// Gets the original source of `expr` if it's unchanged from the parse,
+    /// including any documentation before it
+    fn slice(&self, expr: &Expr) -> Option<&'a str> {
+        if !self.nodes.get(&(expr.start, expr.end))?.contains(&expr) {
+            return None;
+        }
+
+        let leftmost = first_start(expr);
+        let mut start = leftmost;
+
+        if expr.doc.is_some() {
+            let before = self
+                .tokens
+                .iter()
+                .rev()
+                .filter(|(_, span)| span.end <= leftmost);
+
+            for (token, span) in before {
+                match token {
+                    Token::Doc(_) => start = span.start,
+                    Token::Comment(_) => continue,
+                    _ => break,
+                }
+            }
+        }
+
+        self.source.get(start..expr.end)
+    }
+
+    /// Gets the original source of `expr` like [Preserved::slice] but only if
+    /// it's an operation wrapped in its own parenthesis, such as `(1 + 2)`
+    fn grouped(&self, expr: &Expr) -> Option<&'a str> {
+        match &expr.kind {
+            ExprKind::Op(op) if op.grouped => self.slice(expr),
+            _ => None,
+        }
+    }
+}
+
+/// Renders given expressions as an indented tree of each node's kind and the
+/// source range it covers, used for debugging the parser
+pub fn dump_spans(exprs: &[Expr]) -> String {
+    let mut output = String::new();
+
+    for expr in exprs {
+        dump_expr(&mut output, expr, 0);
+    }
+
+    output
+}
+
+/// Writes a single expression and all of its children into `output` for [dump_spans]
+fn dump_expr(output: &mut String, expr: &Expr, depth: usize) {
+    output.push_str(&format!(
+        "{}{} [{}..{}]\n",
+        "  ".repeat(depth),
+        expr.kind.name(),
+        expr.start,
+        expr.end
+    ));
+
+    for child in expr.children() {
+        dump_expr(output, child, depth + 1);
+    }
+}
+
+/// Renders `expr` like its pretty `{:#?}` debug output but without the `start`
+/// and `end` of each expression or any `doc: None`, so that the differences of
+/// a failing comparison are easier to find
+pub fn debug_no_spans(expr: &Expr) -> String {
+    format!("{:#?}", expr)
+        .lines()
+        .filter(|line| {
+            let field = line.trim_start();
+
+            field != "doc: None,"
+                && !["start: ", "end: "].iter().any(|name| {
+                    field
+                        .strip_prefix(name)
+                        .and_then(|rest| rest.strip_suffix(','))
+                        .is_some_and(|num| num.parse::<usize>().is_ok())
+                })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders given expressions as a GraphViz DOT graph, with a node labelled by
+/// the kind of each expression and edges going from it to its children
+pub fn to_dot(exprs: &[Expr]) -> String {
+    let mut output = String::from("digraph ast {\n");
+    let mut count = 0;
+
+    for expr in exprs {
+        dot_expr(&mut output, &mut count, expr);
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+/// Writes a single expression and all of its children into `output` for
+/// [to_dot], giving the numbered id of the node it wrote
+fn dot_expr(output: &mut String, count: &mut usize, expr: &Expr) -> usize {
+    let id = *count;
+    *count += 1;
+
+    output.push_str(&format!(
+        "{}n{} [label=\"{}\"];\n",
+        INDENT,
+        id,
+        expr.kind.name()
+    ));
+
+    for child in expr.children() {
+        let child_id = dot_expr(output, count, child);
+        output.push_str(&format!("{}n{} -> n{};\n", INDENT, id, child_id));
+    }
+
+    id
+}
+
+/// Gets the source range each top-level expression inside of `exprs` covers,
+/// including any documentation attached to it
+fn get_regions(source: &str, exprs: &[Expr]) -> Vec<Range<usize>> {
+    let tokens: Vec<(Token, Range<usize>)> = Token::lexer(source).spanned().collect();
+    let starts: Vec<usize> = exprs
+        .iter()
+        .map(|expr| {
+            let start = first_start(expr);
+
+            let preceding = tokens
+                .iter()
+                .rev()
+                .find(|(token, span)| span.start < start && !matches!(token, Token::Comment(_)));
+
+            match preceding {
+                Some((Token::Doc(_), span)) if expr.doc.is_some() => span.start,
+                _ => start,
+            }
+        })
+        .collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(ind, start)| {
+            let limit = starts.get(ind + 1).copied().unwrap_or(source.len());
+            let end = tokens
+                .iter()
+                .filter(|(_, span)| span.start < limit)
+                .map(|(_, span)| span.end)
+                .next_back()
+                .unwrap_or(limit);
+
+            *start..end
+        })
+        .collect()
+}
+
+/// Finds the leftmost source index of an expression, as an [Op] or [Cast]
+/// starts at its keyword or operator and not its leftmost expression unless
+/// it's wrapped in parenthesis
+fn first_start(expr: &Expr) -> usize {
+    match &expr.kind {
+        ExprKind::Op(op) => expr.start.min(first_start(&op.left)),
+        ExprKind::Cast(cast) => expr.start.min(first_start(&cast.expr)),
+        _ => expr.start,
+    }
+}
+
+/// Renders a single expression at the given indentation `depth`
+fn render(expr: &Expr, depth: usize, keep: Option<&Preserved>) -> String {
+    if let Some(text) = keep.and_then(|keep| keep.slice(expr)) {
+        return text.to_string();
+    }
+
+    let doc = match &expr.doc {
+        Some(doc) => doc
+            .split('\n')
+            .map(|line| match line {
+                "" => format!("---\n{}", INDENT.repeat(depth)),
+                line => format!("--- {}\n{}", line, INDENT.repeat(depth)),
+            })
+            .collect(),
+        None => String::new(),
+    };
+
+    doc + &render_kind(&expr.kind, depth, keep)
+}
+
+/// Renders the underlying kind of an expression at the given indentation `depth`
+fn render_kind(kind: &ExprKind, depth: usize, keep: Option<&Preserved>) -> String {
+    match kind {
+        ExprKind::Body(Body(exprs)) => render_block(exprs, depth, keep),
+        ExprKind::Not(Not(expr)) => format!("!{}", render(expr, depth, keep)),
+        ExprKind::Neg(Neg(expr)) => {
+            let operand = render(expr, depth, keep);
+
+            // `--` would start a comment so operands starting with `-` are grouped,
+            // as are operations apart from `**` which binds tighter than `-`
+            if matches!(&expr.kind, ExprKind::Op(op) if op.kind != OpKind::Pow)
+                || operand.starts_with('-')
+            {
+                format!("-({})", operand)
+            } else {
+                format!("-{}", operand)
+            }
+        }
+        ExprKind::Op(op) => format!(
+            "{} {} {}",
+            render_operand(&op.left, op.kind, false, depth, keep),
+            op.kind,
+            render_operand(&op.right, op.kind, true, depth, keep)
+        ),
+        ExprKind::Path(path) => path.to_string(),
+        ExprKind::Class(class) => {
+            let mut body = vec![];
+
+            if let Some(constructor) = &class.constructor {
+                body.push(Expr::from_parse(
+                    Function {
+                        visibility: constructor.visibility,
+                        path: constructor.path.clone(),
+                        args: constructor.args.clone(),
+                        body: constructor.body.clone(),
+                    },
+                    class.constructor_doc.clone(),
+                    0,
+                ));
+            }
+
+            body.extend(class.body.iter().cloned());
+
+            format!(
+                "{}class {}{} {}",
+                render_visibility(class.visibility),
+                class.id,
+                match &class.parent {
+                    Some(parent) => format!(" : {}", parent),
+                    None => String::new(),
+                },
+                render_block(&body, depth, keep)
+            )
+        }
+        ExprKind::Function(function) => format!(
+            "{}fun {}({}) {}",
+            render_visibility(function.visibility),
+            function.path,
+            render_args(&function.args),
+            render_block(&function.body, depth, keep)
+        ),
+        ExprKind::Method(method) => format!(
+            "{}fun {}({}) {}",
+            render_visibility(method.visibility),
+            method.path,
+            render_args(&method.args),
+            render_block(&method.body, depth, keep)
+        ),
+        ExprKind::FunctionCall(call) => {
+            // a final body argument can only have been a trailing block
+            let (args, trailing) = match call.args.split_last() {
+                Some((last, args)) if matches!(last.kind, ExprKind::Body(_)) => {
+                    (args, format!(" {}", render(last, depth, keep)))
+                }
+                _ => (&call.args[..], String::new()),
+            };
+
+            format!(
+                "{}({}){}",
+                call.path,
+                args.iter()
+                    .map(|arg| render(arg, depth, keep))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                trailing
+            )
+        }
+        ExprKind::Match(matched) => {
+            let inner = INDENT.repeat(depth + 1);
+            let arms: Vec<String> = matched
+                .segments
+                .iter()
+                .map(|seg| {
+                    format!(
+                        "{}{} => {}",
+                        inner,
+                        match &seg.pattern {
+                            Pattern::Expr(expr) => render(expr, depth + 1, keep),
+                            Pattern::Wildcard => "_".to_string(),
+                        },
+                        render(&seg.expr, depth + 1, keep)
+                    )
+                })
+                .collect();
+
+            format!(
+                "match {} {} {{\n{}\n{}}}",
+                matched.kind,
+                render(&matched.condition, depth, keep),
+                arms.join(",\n"),
+                INDENT.repeat(depth)
+            )
+        }
+        ExprKind::If(cond) => {
+            let mut output = cond
+                .segments
+                .iter()
+                .map(|seg| {
+                    format!(
+                        "if {} {}",
+                        render(&seg.condition, depth, keep),
+                        render_block(&seg.body, depth, keep)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(" else ");
+
+            if let Some(IfDefault(body)) = &cond.default {
+                output.push_str(&format!(" else {}", render_block(body, depth, keep)));
+            }
+
+            output
+        }
+        ExprKind::While(looped) => format!(
+            "while {} {}",
+            render(&looped.condition, depth, keep),
+            render_block(&looped.body, depth, keep)
+        ),
+        ExprKind::Return(Return(expr)) => format!("return {}", render(expr, depth, keep)),
+        ExprKind::Let(assigned) => format!(
+            "{}let {}{}{}",
+            render_visibility(assigned.visibility),
+            if assigned.mutable { "mut " } else { "" },
+            assigned.path,
+            match &assigned.expr {
+                Some(expr) => format!(" = {}", render(expr, depth, keep)),
+                None => String::new(),
+            }
+        ),
+        ExprKind::LetSet(set) => format!("{} = {}", set.path, render(&set.expr, depth, keep)),
+        ExprKind::LetCall(LetCall(path)) => path.to_string(),
+        ExprKind::Import(Import(path)) => format!("import {}", path),
+        ExprKind::Cast(cast) => match &cast.expr.kind {
+            // casts only take the operand directly before them
+            ExprKind::Op(_) | ExprKind::Let(_) | ExprKind::LetSet(_) | ExprKind::Return(_) => {
+                format!("({}) as {}", render(&cast.expr, depth, keep), cast.ty)
+            }
+            _ => format!("{} as {}", render(&cast.expr, depth, keep), cast.ty),
+        },
+        ExprKind::CondLet(cond) => {
+            format!("let {} = {}", cond.binding, render(&cond.expr, depth, keep))
+        }
+        ExprKind::IntLit(IntLit(int, radix)) => render_int(*int, *radix),
+        ExprKind::FloatLit(FloatLit(float)) => Token::Float(*float).lexeme(),
+        ExprKind::StrLit(StrLit(string)) => format!("\"{}\"", string),
+        ExprKind::Interpolation(Interpolation(parts)) => format!(
+            "\"{}\"",
+            parts
+                .iter()
+                .map(|part| match &part.kind {
+                    ExprKind::StrLit(StrLit(string)) => string.clone(),
+                    _ => format!("${{{}}}", render(part, depth, keep)),
+                })
+                .collect::<String>()
+        ),
+        ExprKind::CharLit(CharLit(c)) => format!("'{}'", lexer::escape_char(*c)),
+        ExprKind::BoolLit(BoolLit(b)) => b.to_string(),
+        ExprKind::Break => "break".to_string(),
+        ExprKind::None => "none".to_string(),
+    }
+}
+
+/// Renders an integer in the radix it was originally written in, keeping any
+/// sign in front of the prefix such as `-0xFF`
+fn render_int(int: i64, radix: Radix) -> String {
+    let sign = if int < 0 { "-" } else { "" };
+    let abs = int.unsigned_abs();
+
+    match radix {
+        Radix::Decimal => int.to_string(),
+        Radix::Hex => format!("{}0x{:X}", sign, abs),
+        Radix::Binary => format!("{}0b{:b}", sign, abs),
+        Radix::Octal => format!("{}0o{:o}", sign, abs),
+    }
+}
+
+/// Renders an operand of an operation of `kind`, wrapping it in parenthesis if
+/// it's an operation which would otherwise bind differently when parsed again
+fn render_operand(
+    expr: &Expr,
+    kind: OpKind,
+    is_right: bool,
+    depth: usize,
+    keep: Option<&Preserved>,
+) -> String {
+    if let Some(text) = keep.and_then(|keep| keep.grouped(expr)) {
+        return text.to_string(); // already has its own parenthesis
+    }
+
+    match &expr.kind {
+        ExprKind::Op(inner)
+            if inner.kind.precedence() < kind.precedence()
+                || (inner.kind.precedence() == kind.precedence()
+                    && is_right != kind.is_right_associative())
+                || (inner.kind.is_comparison() && kind.is_comparison()) =>
+        {
+            format!("({})", render(expr, depth, keep))
+        }
+        // these take any operations following them, so would take the rest
+        ExprKind::Let(_) | ExprKind::LetSet(_) | ExprKind::Return(_) => {
+            format!("({})", render(expr, depth, keep))
+        }
+        _ => {
+            let operand = render(expr, depth, keep);
+
+            // a leading `-` would take the `**` into its negation instead
+            if kind == OpKind::Pow && !is_right && operand.starts_with('-') {
+                format!("({})", operand)
+            } else {
+                operand
+            }
+        }
+    }
+}
+
+/// Renders a braced body of expressions, placing each on its own line
+fn render_block(exprs: &[Expr], depth: usize, keep: Option<&Preserved>) -> String {
+    if exprs.is_empty() {
+        return "{}".to_string();
+    }
+
+    let inner = INDENT.repeat(depth + 1);
+
+    format!(
+        "{{\n{}\n{}}}",
+        exprs
+            .iter()
+            .map(|expr| format!("{}{}", inner, render(expr, depth + 1, keep)))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        INDENT.repeat(depth)
+    )
+}
+
+/// Renders the `pub ` prefix for public declarations
+fn render_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "pub ",
+        Visibility::Private => "",
+    }
+}
+
+/// Renders subprogram arguments
+fn render_args(args: &[Id]) -> String {
+    args.iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::parser;
+
+    /// Shortcut for parsing a full input
+    fn lparse(input: &str) -> Vec<Expr> {
+        parser::launch(&mut Token::lexer(input)).unwrap()
+    }
+
+    #[test]
+    fn source_basics() {
+        assert_eq!(to_source(&lparse("5   +  3")), "5 + 3\n");
+        assert_eq!(to_source(&lparse("let  mut x")), "let mut x\n");
+        assert_eq!(
+            to_source(&lparse("class Dog:Animal {}")),
+            "class Dog : Animal {}\n"
+        );
+        assert_eq!(to_source(&lparse("\"a ${ b  +  1 }\"")), "\"a ${b + 1}\"\n");
+        assert_eq!(
+            to_source(&lparse("let mut x = 'c' while true { none }")),
+            "let mut x = 'c'\nwhile true {\n    none\n}\n"
+        );
+        assert_eq!(
+            to_source(&lparse("--- Documented\nfun hello(a b) {}")),
+            "--- Documented\nfun hello(a b) {}\n"
+        );
+    }
+
+    #[test]
+    fn source_values() {
+        for input in [
+            "let b = (1 + 2) * 3\n",
+            "let a = (1 < 2) == true\n",
+            "return (1 + 2) * 3\n",
+            "let c = 1 + 2\n",
+            "(let x = 1) + 2\n",
+            "each(items) {\n    x\n}\n",
+            "let p = 2 ** 3 ** 2\n",
+            "let q = (2 ** 3) ** 2\n",
+            "let r = -2 ** 2\n",
+            "let s = (-2) ** 2\n",
+        ] {
+            assert_eq!(to_source(&lparse(input)), input);
+        }
+    }
+
+    #[test]
+    fn source_floats() {
+        let input = "let a = 0.0000001\nlet b = 100000000000000000000.0\nlet c = 2.5\n";
+        assert_eq!(to_source(&lparse(input)), input);
+    }
+
+    #[test]
+    fn source_radixes() {
+        let input = "let a = 0xFF\nlet b = 0b1010 + 0o17\nlet c = -0x1F\n";
+        assert_eq!(to_source(&lparse(input)), input);
+        assert_eq!(to_source(&lparse("0xff 0x00")), "0xFF\n0x0\n");
+        assert_ne!(lparse("0xFF")[0].kind, lparse("255")[0].kind);
+    }
+
+    #[test]
+    fn source_grouping() {
+        let one = Expr::from_parse(IntLit::new(1), None, 0);
+        let sum = Expr::from_parse(
+            Op {
+                left: Box::new(one.clone()),
+                right: Box::new(one.clone()),
+                kind: OpKind::Plus,
+                grouped: false,
+            },
+            None,
+            0,
+        );
+        let product = Expr::from_parse(
+            Op {
+                left: Box::new(sum.clone()),
+                right: Box::new(one.clone()),
+                kind: OpKind::Mul,
+                grouped: false,
+            },
+            None,
+            0,
+        );
+        let nested = Expr::from_parse(
+            Op {
+                left: Box::new(one),
+                right: Box::new(sum),
+                kind: OpKind::Plus,
+                grouped: false,
+            },
+            None,
+            0,
+        );
+
+        assert_eq!(to_source(&[product]), "(1 + 1) * 1\n");
+        assert_eq!(to_source(&[nested]), "1 + (1 + 1)\n");
+        assert_eq!(to_source(&lparse("1 + 2 * 3")), "1 + 2 * 3\n");
+        assert_eq!(to_source(&lparse("1 + 2 as Int")), "1 + 2 as Int\n");
+        assert_eq!(to_source(&lparse("(1 + 2) as Int")), "(1 + 2) as Int\n");
+    }
+
+    #[test]
+    fn source_round_trip() {
+        let input = "class Hi { let x = 2 fun init(x) { 1 } pub fun other(x) { let mut y = 4 } } 1 + 2.5 '\\n' if a {} else if b { 1 } else { 2 } while let x = y {} x as Int as Float foo(1, 2 + 3) a.b = c = 3 -(1 + 2) - -x let a = -(-x)\nlet b = 1";
+        let printed = to_source(&lparse(input));
+
+        assert_eq!(to_source(&lparse(&printed)), printed);
+        assert!(printed.contains("let a = -(-x)\nlet b = 1"));
+    }
+
+    #[test]
+    fn span_dumps() {
+        assert_eq!(
+            dump_spans(&lparse("5 + 3")),
+            "Op [2..5]\n  IntLit [0..1]\n  IntLit [4..5]\n"
+        );
+        assert_eq!(
+            dump_spans(&lparse("while x { none }")),
+            "While [0..16]\n  LetCall [6..7]\n  None [10..14]\n"
+        );
+    }
+
+    #[test]
+    fn spanless_debugging() {
+        assert_eq!(
+            debug_no_spans(&lparse("5 + 3")[0]),
+            "Expr {
+    kind: Op(
+        Op {
+            left: Expr {
+                kind: IntLit(
+                    IntLit(
+                        5,
+                        Decimal,
+                    ),
+                ),
+            },
+            right: Expr {
+                kind: IntLit(
+                    IntLit(
+                        3,
+                        Decimal,
+                    ),
+                ),
+            },
+            kind: Plus,
+            grouped: false,
+        },
+    ),
+}"
+        );
+        assert!(debug_no_spans(&lparse("--- docs\nlet x = none")[0]).contains("doc: Some("));
+    }
+
+    #[test]
+    fn dot_graphs() {
+        let dot = to_dot(&lparse("5 + 3"));
+
+        assert!(dot.starts_with("digraph ast {\n"));
+        assert!(dot.contains("n0 [label=\"Op\"];"));
+        assert!(dot.contains("n1 [label=\"IntLit\"];"));
+        assert!(dot.contains("n2 [label=\"IntLit\"];"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(to_dot(&[]), "digraph ast {\n}\n");
+    }
+
+    #[test]
+    fn preserving_unmodified() {
+        let input = "-- leading comment\n5   +  3\n\n--- Docs for x\nlet  x =   'c'   -- trailing\nwhile true {none}\n";
+
+        assert_eq!(
+            reprint_preserving(input, &lparse(input), &lparse(input)),
+            input
+        );
+    }
+
+    #[test]
+    fn preserving_modified() {
+        let input = "5   +  3\nlet  x =   2\nwhile true {none}\n";
+        let original = lparse(input);
+        let mut parsed = original.clone();
+
+        if let ExprKind::Let(assigned) = &mut parsed[1].kind {
+            assigned.mutable = true;
+        }
+
+        assert_eq!(
+            reprint_preserving(input, &original, &parsed),
+            "5   +  3\nlet mut x = 2\nwhile true {none}\n"
+        );
+    }
+
+    #[test]
+    fn preserving_subtrees() {
+        let input = "fun f() {\n  let  a =   (1  +  2)  *  3\n  y  +  1\n}\n";
+        let original = lparse(input);
+        let mut parsed = original.clone();
+
+        if let ExprKind::Function(function) = &mut parsed[0].kind {
+            if let ExprKind::Let(assigned) = &mut function.body[0].kind {
+                assigned.mutable = true;
+            }
+        }
+
+        assert_eq!(
+            reprint_preserving(input, &original, &parsed),
+            "fun f() {\n    let mut a = (1  +  2)  *  3\n    y  +  1\n}\n"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! Resolution of `import` statements across modules, linking each import to the
+//! top-level declaration it refers to. This is currently flat, so imports can
+//! only refer to declarations made directly inside of another module
+
+use super::{ast::*, symbols};
+use std::{collections::HashMap, fmt};
+
+/// Error found whilst resolving modules
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// Import of a path which isn't declared by any module, along with the
+    /// module the import is inside of and the index it was found at
+    UnresolvedImport(Path, Path, usize),
+
+    /// Import of a declaration which exists but isn't marked `pub`, along with
+    /// the module the import is inside of and the index it was found at
+    PrivateImport(Path, Path, usize),
+}
+
+impl ResolveError {
+    /// Module the failed import is inside of and the index it was found at
+    fn location(&self) -> (String, usize) {
+        match self {
+            ResolveError::UnresolvedImport(_, module, start)
+            | ResolveError::PrivateImport(_, module, start) => (module.to_string(), *start),
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::UnresolvedImport(path, module, _) => {
+                write!(
+                    f,
+                    "Import '{}' in module '{}' couldn't be found",
+                    path, module
+                )
+            }
+            ResolveError::PrivateImport(path, module, _) => {
+                write!(
+                    f,
+                    "Import '{}' in module '{}' isn't public, add `pub` to its declaration",
+                    path, module
+                )
+            }
+        }
+    }
+}
+
+/// Symbols declared across a set of modules, made using [resolve]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolTable {
+    /// Top-level declarations keyed by their fully-qualified path, i.e. `b`
+    /// declared inside of the module `a` as `a.b`
+    pub declarations: HashMap<Path, Expr>,
+
+    /// Fully-qualified paths imported by each module, keyed by module path
+    pub imports: HashMap<Path, Vec<Path>>,
+}
+
+/// Resolves the imports of each module from `modules`, keyed by their module
+/// path, into a [SymbolTable] or gives every import which couldn't be resolved.
+/// Only declarations marked `pub` may be imported from another module
+pub fn resolve(modules: HashMap<Path, Vec<Expr>>) -> Result<SymbolTable, Vec<ResolveError>> {
+    let mut table = SymbolTable::default();
+    let mut imports = vec![];
+
+    for (module, exprs) in modules {
+        for expr in exprs {
+            if let ExprKind::Import(Import(path)) = &expr.kind {
+                imports.push((module.clone(), path.clone(), expr.start));
+            } else if let Some((name, _)) = symbols::declaration(&expr) {
+                table.declarations.insert(qualify(&module, &name), expr);
+            }
+        }
+    }
+
+    let mut errors = vec![];
+
+    for (module, path, start) in imports {
+        match table.declarations.get(&path).map(visibility) {
+            Some(Visibility::Public) => table.imports.entry(module).or_default().push(path),
+            Some(Visibility::Private) => {
+                errors.push(ResolveError::PrivateImport(path, module, start))
+            }
+            None => errors.push(ResolveError::UnresolvedImport(path, module, start)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(table)
+    } else {
+        errors.sort_by_key(ResolveError::location);
+        Err(errors)
+    }
+}
+
+/// Visibility of a top-level declaration found by [symbols::declaration]
+fn visibility(expr: &Expr) -> Visibility {
+    match &expr.kind {
+        ExprKind::Function(function) => function.visibility,
+        ExprKind::Class(class) => class.visibility,
+        ExprKind::Let(assigned) => assigned.visibility,
+        _ => Visibility::Private,
+    }
+}
+
+/// Qualifies `name` declared inside of `module`, i.e. `b` inside of `a` into `a.b`
+fn qualify(module: &Path, name: &Path) -> Path {
+    let mut fields = module.fields.clone();
+    fields.push(module.id.clone());
+    fields.extend(name.fields.iter().cloned());
+
+    Path {
+        fields,
+        id: name.id.clone(),
+        affixed: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend;
+
+    /// Parses each module of `sources`, keyed by their module path
+    fn modules(sources: &[(&str, &str)]) -> HashMap<Path, Vec<Expr>> {
+        sources
+            .iter()
+            .map(|(module, source)| (Path::new(*module), frontend::parse(source).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn imports() {
+        let table = resolve(modules(&[("a", "pub fun b() {}"), ("main", "import a.b")])).unwrap();
+        let imported = Path {
+            fields: vec!["a".into()],
+            id: "b".into(),
+            affixed: false,
+        };
+
+        assert!(matches!(
+            table.declarations[&imported].kind,
+            ExprKind::Function(_)
+        ));
+        assert_eq!(table.imports[&Path::new("main")], vec![imported]);
+    }
+
+    #[test]
+    fn unresolved_imports() {
+        assert_eq!(
+            resolve(modules(&[("a", "let c = 1"), ("main", "5\nimport a.b")])),
+            Err(vec![ResolveError::UnresolvedImport(
+                Path {
+                    fields: vec!["a".into()],
+                    id: "b".into(),
+                    affixed: false,
+                },
+                Path::new("main"),
+                2
+            )])
+        );
+    }
+
+    #[test]
+    fn private_imports() {
+        let imported = Path {
+            fields: vec!["a".into()],
+            id: "c".into(),
+            affixed: false,
+        };
+
+        assert_eq!(
+            resolve(modules(&[("a", "let c = 1 + 2"), ("main", "import a.c")])),
+            Err(vec![ResolveError::PrivateImport(
+                imported.clone(),
+                Path::new("main"),
+                0
+            )])
+        );
+        assert_eq!(
+            resolve(modules(&[
+                ("a", "pub let c = 1 + 2"),
+                ("main", "import a.c")
+            ]))
+            .unwrap()
+            .imports[&Path::new("main")],
+            vec![imported]
+        );
+    }
+}
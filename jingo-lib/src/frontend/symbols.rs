@@ -0,0 +1,99 @@
+//! Symbol gathering for declarations found within the abstract syntax tree, used
+//! as a precursor to code generation
+
+use super::ast::*;
+
+/// Kind of declaration a [SymbolEntry] was made from
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolKind {
+    Function,
+    Class,
+    Let,
+}
+
+/// Single declared symbol, found from a top-level declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolEntry {
+    /// Name of the declared symbol
+    pub name: Path,
+
+    /// Kind of declaration this symbol was made from
+    pub kind: SymbolKind,
+
+    /// Starting index of the declaration
+    pub start: usize,
+}
+
+/// Gets the name and kind of symbol `expr` declares, if it's a `fun`, `class`
+/// or `let` declaration
+pub fn declaration(expr: &Expr) -> Option<(Path, SymbolKind)> {
+    match &expr.kind {
+        ExprKind::Function(function) => Some((function.path.clone(), SymbolKind::Function)),
+        ExprKind::Class(class) => Some((Path::new(class.id.0.clone()), SymbolKind::Class)),
+        ExprKind::Let(assigned) => Some((assigned.path.clone(), SymbolKind::Let)),
+        _ => None,
+    }
+}
+
+/// Gets every top-level `fun`, `class` and `let` declaration from given
+/// expressions in order of appearance
+pub fn symbol_table(exprs: &[Expr]) -> Vec<SymbolEntry> {
+    exprs
+        .iter()
+        .filter_map(|expr| {
+            let (name, kind) = declaration(expr)?;
+
+            Some(SymbolEntry {
+                name,
+                kind,
+                start: expr.start,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::{lexer::Token, parser};
+    use logos::Logos;
+
+    #[test]
+    fn operation_values() {
+        let exprs = parser::launch(&mut Token::lexer("let c = 1 + 2")).unwrap();
+
+        assert_eq!(
+            declaration(&exprs[0]),
+            Some((Path::new("c"), SymbolKind::Let))
+        );
+    }
+
+    #[test]
+    fn table_basics() {
+        let exprs = parser::launch(&mut Token::lexer(
+            "fun main() {} 5 class Hi { let y = 2 } let x = 1",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            symbol_table(&exprs),
+            vec![
+                SymbolEntry {
+                    name: Path::new("main"),
+                    kind: SymbolKind::Function,
+                    start: 0
+                },
+                SymbolEntry {
+                    name: Path::new("Hi"),
+                    kind: SymbolKind::Class,
+                    start: 16
+                },
+                SymbolEntry {
+                    name: Path::new("x"),
+                    kind: SymbolKind::Let,
+                    start: 39
+                }
+            ]
+        );
+    }
+}
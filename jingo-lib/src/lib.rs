@@ -3,7 +3,6 @@
 #![deny(unsafe_code)]
 #![deny(warnings)]
 #![allow(incomplete_features)]
-#![feature(bool_to_option)]
-#![feature(if_let_guard)]
 
 pub mod frontend;
+pub mod meta;
@@ -0,0 +1,98 @@
+//! Metadata for keeping track of where something is within a source file
+
+use std::path::PathBuf;
+
+/// Line and column position within a file, both starting from `1`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetaPos {
+    /// Line number
+    pub line: usize,
+
+    /// Column number
+    pub col: usize,
+}
+
+impl MetaPos {
+    /// Creates new [MetaPos] at the very start of a file
+    pub fn new() -> Self {
+        Self { line: 1, col: 1 }
+    }
+
+    /// Moves down by `amount` lines, resetting the column to the start
+    pub fn newline(&mut self, amount: usize) {
+        self.line += amount;
+        self.col = 1;
+    }
+
+    /// Moves past all characters in `text`, going down a line for each newline
+    pub fn advance(&mut self, text: &str) {
+        for c in text.chars() {
+            if c == '\n' {
+                self.newline(1);
+            } else {
+                self.col += 1;
+            }
+        }
+    }
+}
+
+impl Default for MetaPos {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Metadata of a file being read, containing the current position and the
+/// optional path of the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meta {
+    /// Current position within the file
+    pub pos: MetaPos,
+
+    /// Path to file, if any
+    pub path: Option<PathBuf>,
+}
+
+impl Meta {
+    /// Creates new [Meta] at the start of the file found at `path`
+    pub fn new(path: impl Into<Option<PathBuf>>) -> Self {
+        Self {
+            pos: MetaPos::new(),
+            path: path.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let mut pos = MetaPos::new();
+
+        assert_eq!(pos, MetaPos { line: 1, col: 1 });
+        assert_eq!(pos, MetaPos::default());
+
+        pos.col += 4;
+        pos.newline(2);
+
+        assert_eq!(pos, MetaPos { line: 3, col: 1 });
+
+        pos.advance("ab\ncdé");
+
+        assert_eq!(pos, MetaPos { line: 4, col: 4 });
+    }
+
+    #[test]
+    fn meta_paths() {
+        assert_eq!(
+            Meta::new(PathBuf::from("hello.jno")),
+            Meta {
+                pos: MetaPos { line: 1, col: 1 },
+                path: Some(PathBuf::from("hello.jno"))
+            }
+        );
+        assert_eq!(Meta::new(None).path, None);
+    }
+}